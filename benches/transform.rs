@@ -0,0 +1,61 @@
+//! `cargo bench`: the plugin over a module with 1000 elements, with nothing
+//! to match names against, with `excluded` and `libraries` set, and with a
+//! long `excluded` list.
+
+#![feature(test)]
+
+extern crate test;
+
+use swc_core::ecma::ast::Program;
+use swc_core::ecma::parser::{Syntax, TsSyntax};
+use swc_core::ecma::transforms::testing::Tester;
+use swc_plugin_react_source_string::{parse_config, transform};
+use test::Bencher;
+
+/// 200 rows of five elements each, host elements and imported components.
+fn source() -> String {
+    let rows: String = (0..200)
+        .map(|i| {
+            format!(
+                "<li key={{{i}}}><Card><span>{i}</span><Button onClick={{f}} /><a href=\"#\" /></Card></li>\n"
+            )
+        })
+        .collect();
+    format!(
+        "import {{ Button, Card }} from \"@acme/ui\";\nexport const List = () => <>{rows}</>;\n"
+    )
+}
+
+fn bench_config(b: &mut Bencher, config: &str) {
+    let config = parse_config(Some(config)).expect("valid config");
+    Tester::run(|tester| {
+        let syntax = Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        });
+        let module = tester.with_parser("App.tsx", syntax, &source(), |p| p.parse_module())?;
+        let program = Program::Module(module);
+        b.iter(|| transform(program.clone(), &*tester.cm, &tester.comments, &config));
+        Ok(())
+    });
+}
+
+#[bench]
+fn nothing_to_match(b: &mut Bencher) {
+    bench_config(b, "{}");
+}
+
+#[bench]
+fn excluded_and_libraries(b: &mut Bencher) {
+    bench_config(
+        b,
+        r#"{ "excluded": ["Dialog"], "libraries": { "@acme/ui": { "attribute": "data-ui" } } }"#,
+    );
+}
+
+/// Each element is looked up in a 500-name `excluded` set.
+#[bench]
+fn many_exclusions(b: &mut Bencher) {
+    let names: Vec<String> = (0..500).map(|i| format!("\"Widget{i}\"")).collect();
+    bench_config(b, &format!(r#"{{ "excluded": [{}] }}"#, names.join(", ")));
+}
@@ -1,51 +1,1013 @@
 //! SWC plugin: adds `data-source="path:line"` to JSX elements (HTML and configured UI library components).
 //! Equivalent to the Babel plugin `babel-plugin-react-source-string`.
 
-use serde::Deserialize;
-use std::collections::HashSet;
-use swc_core::common::{SourceMapper, SyntaxContext, DUMMY_SP};
+use glob::{MatchOptions, Pattern};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use swc_core::common::comments::{Comment, CommentKind, Comments};
+use swc_core::common::errors::HANDLER;
+use swc_core::common::plugin::metadata::TransformPluginMetadataContextKind;
+use swc_core::common::sync::Lrc;
+use swc_core::common::{BytePos, FileName, SourceMapper, Span, Spanned, SyntaxContext, DUMMY_SP};
 use swc_core::ecma::ast::*;
 use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 use swc_core::plugin::{plugin_transform, proxies::TransformPluginProgramMetadata};
 
 #[derive(Debug, Default, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct PluginConfig {
     excluded: Option<Vec<String>>,
+    #[serde(alias = "rootDir")]
     root: Option<String>,
+    include_column: Option<bool>,
+    position_kind: Option<PositionKind>,
+    attribute_name: Option<String>,
+    format: Option<String>,
+    split_attributes: Option<bool>,
+    include_component_name: Option<bool>,
+    include_files: Option<Vec<String>>,
+    exclude_files: Option<Vec<String>>,
+    extensions: Option<Vec<String>>,
+    absolute_paths: Option<bool>,
+    path_style: Option<PathStyle>,
+    mode: Option<Mode>,
+    overwrite_existing: Option<bool>,
+    include_range: Option<bool>,
+    hash_paths: Option<bool>,
+    emit_manifest: Option<bool>,
+    tag_fragments: Option<bool>,
+    case_sensitive_exclusions: Option<bool>,
+    root_only: Option<bool>,
+    component_boundary_only: Option<bool>,
+    styled_components: Option<bool>,
+    strip_extension: Option<bool>,
+    leading_dot_slash: Option<bool>,
+    url_encode_path: Option<bool>,
+    excluded_patterns: Option<Vec<String>>,
+    ignored_member_objects: Option<Vec<String>>,
+    html_only: Option<bool>,
+    valid_html_tags_only: Option<bool>,
+    custom_elements: Option<Vec<String>>,
+    component_detection: Option<ComponentDetection>,
+    framework: Option<Framework>,
+    components_from_imports_only: Option<bool>,
+    path_separator: Option<PathSeparator>,
+    case_insensitive_paths: Option<bool>,
+    line_offset: Option<i32>,
+    skip_on_spread: Option<bool>,
+    include_module: Option<bool>,
+    include_snippet: Option<bool>,
+    content_hash: Option<bool>,
+    fallback_to_parent_span: Option<bool>,
+    snippet_length: Option<usize>,
+    libraries: Option<Libraries>,
+    warn_unused_libraries: Option<bool>,
+    value_kind: Option<ValueKind>,
+    emit_as: Option<EmitAs>,
+    exclude_subtree: Option<bool>,
+    tag_but_skip_children: Option<Vec<String>>,
+    prepend_attribute: Option<bool>,
+    spread_precedence: Option<SpreadPrecedence>,
+    insert_after: Option<String>,
+    max_depth: Option<usize>,
+    zero_based_lines: Option<bool>,
+    path_prefix: Option<String>,
+    separator: Option<String>,
+    column_separator: Option<String>,
+    only_interactive: Option<bool>,
+    require_click_handler: Option<bool>,
+    included: Option<Vec<String>>,
+    quote_style: Option<QuoteStyle>,
+    include_self_closing: Option<bool>,
+    include_prop_count: Option<bool>,
+    cwd_candidates: Option<Vec<String>>,
+    include_element_id: Option<bool>,
+    opt_out_attribute: Option<String>,
+    clean_filenames: Option<bool>,
+    raw_filename: Option<bool>,
+    dry_run: Option<bool>,
+    ignore_patterns: Option<Vec<String>>,
+    disable_directive: Option<String>,
+    attribute_prefix: Option<String>,
+}
+
+/// `libraries`, as a list of import sources or a map of overrides.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Libraries {
+    /// `["@mui/material", "*"]`: entries without overrides.
+    List(Vec<String>),
+    Map(HashMap<String, LibraryConfig>),
+}
+
+impl Libraries {
+    fn into_map(self) -> HashMap<String, LibraryConfig> {
+        match self {
+            Libraries::List(modules) => modules
+                .into_iter()
+                .map(|module| (module, LibraryConfig::default()))
+                .collect(),
+            Libraries::Map(map) => map,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Libraries::List(modules) => modules.is_empty(),
+            Libraries::Map(map) => map.is_empty(),
+        }
+    }
+}
+
+/// Per-module overrides, keyed by import source in `libraries`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct LibraryConfig {
+    /// Replaces `attributeName` for components imported from the module.
+    attribute: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    #[default]
+    Development,
+    /// Leave the program untouched.
+    Production,
+    /// `Production` when SWC's environment (`SWC_ENV`, `NODE_ENV`, or
+    /// `"development"`) is `"production"`, see [`Mode::resolve`].
+    Auto,
+}
+
+impl Mode {
+    /// Resolves `Auto` against SWC's environment name `env`.
+    fn resolve(self, env: Option<&str>) -> Self {
+        match (self, env) {
+            (Mode::Auto, Some("production")) => Mode::Production,
+            (Mode::Auto, _) => Mode::Development,
+            (mode, _) => mode,
+        }
+    }
+}
+
+/// How a JSX element name is told apart from a host element, for the
+/// filters that treat the two differently.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ComponentDetection {
+    /// Any uppercase letter makes a component.
+    #[default]
+    HasUpper,
+    /// An uppercase first letter makes a component, as in React.
+    FirstUpper,
+    /// Only lowercase known HTML/SVG tags and `customElements` are host
+    /// elements.
+    NotHtmlTag,
+}
+
+/// The JSX flavour being compiled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Framework {
+    #[default]
+    React,
+    /// Vue JSX, where `<my-card>` may be a component resolved from the
+    /// setup scope.
+    Vue,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+enum PathSeparator {
+    #[default]
+    #[serde(rename = "/")]
+    Slash,
+    #[serde(rename = "\\")]
+    Backslash,
+    /// Backslashes if SWC reported the filename with backslashes.
+    #[serde(rename = "auto")]
+    Auto,
+}
+
+/// Where the location goes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum EmitAs {
+    /// `data-source="…"` on the element.
+    #[default]
+    Attribute,
+    /// `/* data-source: … */` in front of the element.
+    Comment,
+}
+
+/// What locates the element within its file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum PositionKind {
+    /// `path:line`, plus the column with `includeColumn`.
+    #[default]
+    Line,
+    /// `path:offset`, the byte offset of the element in the file.
+    Offset,
+    /// `path:line:column`, the same as `includeColumn`.
+    LineColumn,
+}
+
+/// Which value wins when a spread may carry one of the injected attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SpreadPrecedence {
+    /// Inject after the last spread.
+    Ours,
+    /// Inject before the first spread.
+    Theirs,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ValueKind {
+    /// `data-source="path:line"`
+    #[default]
+    String,
+    /// `data-source={{ file: "path", line: 1 }}`
+    Object,
+}
+
+/// Quotes written to the `raw` of generated strings; without one the code
+/// generator picks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum QuoteStyle {
+    Double,
+    Single,
+}
+
+impl QuoteStyle {
+    /// `value` in quotes, or `None` if it would need escaping (which JSX
+    /// attribute strings don't support).
+    fn quote(self, value: &str) -> Option<String> {
+        let quote = match self {
+            QuoteStyle::Double => '"',
+            QuoteStyle::Single => '\'',
+        };
+        if value.contains([quote, '\\', '&', '\n', '\r']) {
+            return None;
+        }
+        Some(format!("{quote}{value}{quote}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PathStyle {
+    /// Relative to `root` / `cwd` when known.
+    #[default]
+    Relative,
+    /// The filename as SWC reports it.
+    Absolute,
+    /// Only the last path segment.
+    Basename,
 }
 
-struct ParsedConfig {
+const DEFAULT_ATTRIBUTE_NAME: &str = "data-source";
+const DEFAULT_SEPARATOR: &str = ":";
+/// Characters kept by `includeSnippet` unless `snippetLength` is set.
+const DEFAULT_SNIPPET_LENGTH: usize = 40;
+const DEFAULT_OPT_OUT_ATTRIBUTE: &str = "data-no-source";
+const DEFAULT_DISABLE_DIRECTIVE: &str = "@no-react-source";
+/// Longest element name lowercased on the stack for `excluded` lookups.
+const LOWERCASE_BUFFER_LEN: usize = 64;
+
+/// `libraries` key matching components imported from any module.
+const ANY_LIBRARY: &str = "*";
+const COMPONENT_ATTRIBUTE_NAME: &str = "data-component";
+
+/// Plugin options after defaults and validation, see [`parse_config`].
+pub struct ParsedConfig {
     excluded: HashSet<String>,
+    case_sensitive_exclusions: bool,
     root: Option<String>,
+    include_column: bool,
+    position_kind: PositionKind,
+    attribute_name: String,
+    format: Option<String>,
+    split_attributes: bool,
+    include_component_name: bool,
+    include_files: Vec<Pattern>,
+    exclude_files: Vec<Pattern>,
+    /// Lowercased, each with a leading `.`.
+    extensions: Vec<String>,
+    path_style: PathStyle,
+    mode: Mode,
+    overwrite_existing: bool,
+    include_range: bool,
+    hash_paths: bool,
+    emit_manifest: bool,
+    tag_fragments: bool,
+    root_only: bool,
+    component_boundary_only: bool,
+    styled_components: bool,
+    strip_extension: bool,
+    leading_dot_slash: bool,
+    url_encode_path: bool,
+    excluded_patterns: Vec<Regex>,
+    /// Roots of member elements that are never tagged: `Styled` for
+    /// `<Styled.Button>`.
+    ignored_member_objects: HashSet<String>,
+    html_only: bool,
+    valid_html_tags_only: bool,
+    custom_elements: HashSet<String>,
+    component_detection: ComponentDetection,
+    framework: Framework,
+    components_from_imports_only: bool,
+    path_separator: PathSeparator,
+    case_insensitive_paths: bool,
+    line_offset: i32,
+    skip_on_spread: bool,
+    include_module: bool,
+    include_snippet: bool,
+    content_hash: bool,
+    fallback_to_parent_span: bool,
+    snippet_length: usize,
+    /// Import source -> attribute name, from `libraries`.
+    library_attributes: HashMap<String, String>,
+    /// Every `libraries` key, with or without an attribute.
+    library_keys: HashSet<String>,
+    warn_unused_libraries: bool,
+    value_kind: ValueKind,
+    emit_as: EmitAs,
+    exclude_subtree: bool,
+    /// Elements that are tagged but whose children and props are not.
+    tag_but_skip_children: HashSet<String>,
+    prepend_attribute: bool,
+    spread_precedence: Option<SpreadPrecedence>,
+    insert_after: Option<String>,
+    max_depth: Option<usize>,
+    zero_based_lines: bool,
+    path_prefix: String,
+    /// Between path and line.
+    separator: String,
+    /// Between line and column.
+    column_separator: String,
+    only_interactive: bool,
+    require_click_handler: bool,
+    /// Like `excluded`: lowercased unless `case_sensitive_exclusions`.
+    included: HashSet<String>,
+    quote_style: Option<QuoteStyle>,
+    include_self_closing: bool,
+    include_prop_count: bool,
+    cwd_candidates: Vec<String>,
+    include_element_id: bool,
+    /// Marker removed from elements that must not be tagged.
+    opt_out_attribute: String,
+    clean_filenames: bool,
+    raw_filename: bool,
+    dry_run: bool,
+    ignore_patterns: Vec<IgnoreRule>,
+    /// Comment word that leaves the whole file untouched.
+    disable_directive: String,
+    /// `data-component`, with `attributePrefix` applied.
+    component_attribute_name: String,
+}
+
+impl Default for ParsedConfig {
+    fn default() -> Self {
+        Self {
+            excluded: HashSet::new(),
+            case_sensitive_exclusions: false,
+            root: None,
+            include_column: false,
+            position_kind: PositionKind::Line,
+            attribute_name: DEFAULT_ATTRIBUTE_NAME.to_string(),
+            format: None,
+            split_attributes: false,
+            include_component_name: false,
+            include_files: Vec::new(),
+            exclude_files: Vec::new(),
+            extensions: Vec::new(),
+            path_style: PathStyle::Relative,
+            mode: Mode::Development,
+            overwrite_existing: false,
+            include_range: false,
+            hash_paths: false,
+            emit_manifest: false,
+            tag_fragments: false,
+            root_only: false,
+            component_boundary_only: false,
+            styled_components: false,
+            strip_extension: false,
+            leading_dot_slash: false,
+            url_encode_path: false,
+            excluded_patterns: Vec::new(),
+            ignored_member_objects: HashSet::new(),
+            html_only: false,
+            valid_html_tags_only: false,
+            custom_elements: HashSet::new(),
+            component_detection: ComponentDetection::HasUpper,
+            framework: Framework::React,
+            components_from_imports_only: false,
+            path_separator: PathSeparator::Slash,
+            case_insensitive_paths: false,
+            line_offset: 0,
+            skip_on_spread: false,
+            include_module: false,
+            include_snippet: false,
+            content_hash: false,
+            fallback_to_parent_span: false,
+            snippet_length: DEFAULT_SNIPPET_LENGTH,
+            library_attributes: HashMap::new(),
+            library_keys: HashSet::new(),
+            warn_unused_libraries: false,
+            value_kind: ValueKind::String,
+            emit_as: EmitAs::Attribute,
+            exclude_subtree: false,
+            tag_but_skip_children: HashSet::new(),
+            prepend_attribute: false,
+            spread_precedence: None,
+            insert_after: None,
+            max_depth: None,
+            zero_based_lines: false,
+            path_prefix: String::new(),
+            separator: DEFAULT_SEPARATOR.to_string(),
+            column_separator: DEFAULT_SEPARATOR.to_string(),
+            only_interactive: false,
+            require_click_handler: false,
+            included: HashSet::new(),
+            quote_style: None,
+            include_self_closing: false,
+            include_prop_count: false,
+            cwd_candidates: Vec::new(),
+            include_element_id: false,
+            opt_out_attribute: DEFAULT_OPT_OUT_ATTRIBUTE.to_string(),
+            clean_filenames: false,
+            raw_filename: false,
+            dry_run: false,
+            ignore_patterns: Vec::new(),
+            disable_directive: DEFAULT_DISABLE_DIRECTIVE.to_string(),
+            component_attribute_name: COMPONENT_ATTRIBUTE_NAME.to_string(),
+        }
+    }
+}
+
+/// Parses the plugin options JSON, `None` meaning no options were given.
+///
+/// Returns `None` (after reporting an error) when the config can't be used.
+/// Problems that can be worked around are reported as warnings, all of them
+/// at once. They go to SWC's `HANDLER` and are dropped outside a handler
+/// scope; use [`validate_config`] to get them back instead.
+pub fn parse_config(config_str: Option<&str>) -> Option<ParsedConfig> {
+    let problems = ConfigProblems::default();
+    let config = resolve_config(config_str, &problems);
+    let report = if config.is_some() {
+        config_warning
+    } else {
+        config_error
+    };
+    for message in problems.0.into_inner() {
+        report(&message);
+    }
+    config
+}
+
+/// Like [`parse_config`], but strict: any problem at all, from an unknown
+/// option to a conflicting pair of flags, is an error. `Err` lists every
+/// problem found, `Ok` holds the config with all defaults applied.
+pub fn validate_config(config_str: Option<&str>) -> Result<ParsedConfig, Vec<String>> {
+    let problems = ConfigProblems::default();
+    let config = resolve_config(config_str, &problems);
+    let problems = problems.0.into_inner();
+    match config {
+        Some(config) if problems.is_empty() => Ok(config),
+        _ => Err(problems),
+    }
+}
+
+/// Configuration problems, collected so they can be reported together.
+#[derive(Default)]
+struct ConfigProblems(RefCell<Vec<String>>);
+
+impl ConfigProblems {
+    fn report(&self, message: &str) {
+        self.0.borrow_mut().push(message.to_string());
+    }
 }
 
-fn parse_config(metadata: &TransformPluginProgramMetadata) -> ParsedConfig {
-    let config_str = match metadata.get_transform_plugin_config() {
-        Some(s) => s,
-        None => {
-            return ParsedConfig {
-                excluded: HashSet::new(),
-                root: None,
+/// Deserializes the options. On failure each option is tried on its own, so
+/// that every unknown key and mistyped value is reported, not just the first.
+fn deserialize_config(config_str: &str, problems: &ConfigProblems) -> Option<PluginConfig> {
+    let error = match serde_json::from_str(config_str) {
+        Ok(config) => return Some(config),
+        Err(e) => e,
+    };
+    let mut reported = false;
+    if let Ok(serde_json::Value::Object(options)) = serde_json::from_str(config_str) {
+        for (key, value) in options {
+            let option = serde_json::Map::from_iter([(key, value)]);
+            if let Err(e) = serde_json::from_value::<PluginConfig>(option.into()) {
+                problems.report(&format!("invalid plugin config: {e}"));
+                reported = true;
             }
         }
+    }
+    // Not an object, or each option is fine alone (`root` and `rootDir`).
+    if !reported {
+        problems.report(&format!("invalid plugin config: {error}"));
+    }
+    None
+}
+
+/// Builds the config, reporting what it had to work around to `problems`.
+fn resolve_config(config_str: Option<&str>, problems: &ConfigProblems) -> Option<ParsedConfig> {
+    let Some(config_str) = config_str else {
+        return Some(ParsedConfig::default());
+    };
+    let config = deserialize_config(config_str, problems)?;
+    let case_sensitive = config.case_sensitive_exclusions.unwrap_or(false);
+    let attribute_prefix = config.attribute_prefix.as_deref().filter(|prefix| {
+        let valid = prefix.is_empty() || is_valid_attribute_name(prefix);
+        if !valid {
+            problems.report(&format!(
+                "`attributePrefix` {prefix:?} is not valid in a JSX attribute name and is ignored"
+            ));
+        }
+        valid && !prefix.is_empty()
+    });
+    let prefixed = |name: String| match attribute_prefix {
+        Some(prefix) => prefix_attribute_name(prefix, &name),
+        None => name,
     };
-    let config: PluginConfig = match serde_json::from_str(&config_str) {
-        Ok(c) => c,
-        Err(_) => {
-            return ParsedConfig {
-                excluded: HashSet::new(),
-                root: None,
+    let separator = non_empty(problems, "separator", config.separator)
+        .unwrap_or_else(|| DEFAULT_SEPARATOR.to_string());
+    let column_separator = non_empty(problems, "columnSeparator", config.column_separator)
+        .unwrap_or_else(|| separator.clone());
+    let emit_as = config.emit_as.unwrap_or_default();
+    let mut value_kind = config.value_kind.unwrap_or_default();
+    if emit_as == EmitAs::Comment {
+        if value_kind == ValueKind::Object {
+            problems.report("`valueKind: \"object\"` is ignored with `emitAs: \"comment\"`");
+            value_kind = ValueKind::String;
+        }
+        if config.split_attributes == Some(true) {
+            problems.report("`splitAttributes` is ignored with `emitAs: \"comment\"`");
+        }
+    }
+    if value_kind == ValueKind::Object {
+        if config.split_attributes == Some(true) {
+            problems.report("`splitAttributes` is ignored with `valueKind: \"object\"`");
+        }
+        if config.format.is_some() {
+            problems.report("`format` is ignored with `valueKind: \"object\"`");
+        }
+    }
+    if config.split_attributes == Some(true)
+        && config.format.is_some()
+        && value_kind == ValueKind::String
+        && emit_as == EmitAs::Attribute
+    {
+        problems.report("`format` is ignored with `splitAttributes`");
+    }
+    if config.absolute_paths.is_some() && config.path_style.is_some() {
+        problems.report("`absolutePaths` is ignored with `pathStyle`");
+    }
+    if config.root_only == Some(true) && config.max_depth.is_some_and(|max| max > 0) {
+        problems.report("`maxDepth` has no effect with `rootOnly`");
+    }
+    if config.html_only == Some(true) {
+        // Both only apply to components, which `htmlOnly` never tags.
+        if config.include_module == Some(true) {
+            problems.report("`includeModule` has no effect with `htmlOnly`");
+        }
+        if config.libraries.as_ref().is_some_and(|l| !l.is_empty()) {
+            problems.report("`libraries` has no effect with `htmlOnly`");
+        }
+    }
+    if config.raw_filename == Some(true) {
+        let ignored = [
+            ("pathStyle", config.path_style.is_some()),
+            ("absolutePaths", config.absolute_paths == Some(true)),
+            (
+                "pathPrefix",
+                config.path_prefix.as_ref().is_some_and(|p| !p.is_empty()),
+            ),
+            ("hashPaths", config.hash_paths == Some(true)),
+            ("stripExtension", config.strip_extension == Some(true)),
+            ("urlEncodePath", config.url_encode_path == Some(true)),
+            ("leadingDotSlash", config.leading_dot_slash == Some(true)),
+            ("pathSeparator", config.path_separator.is_some()),
+            ("cleanFilenames", config.clean_filenames == Some(true)),
+        ];
+        for (option, set) in ignored {
+            if set {
+                problems.report(&format!("`{option}` is ignored with `rawFilename`"));
             }
         }
+    }
+    let libraries = config
+        .libraries
+        .map(Libraries::into_map)
+        .unwrap_or_default();
+    let name_set = |names: Option<Vec<String>>| -> HashSet<String> {
+        names
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| if case_sensitive { s } else { s.to_lowercase() })
+            .collect()
     };
-    ParsedConfig {
-        excluded: config
-            .excluded
-            .map(|v| v.into_iter().map(|s| s.to_lowercase()).collect())
-            .unwrap_or_default(),
+    Some(ParsedConfig {
+        excluded: name_set(config.excluded),
+        included: name_set(config.included),
+        case_sensitive_exclusions: case_sensitive,
         root: config.root,
+        include_column: config.include_column.unwrap_or(false)
+            || config.position_kind == Some(PositionKind::LineColumn),
+        position_kind: config.position_kind.unwrap_or_default(),
+        attribute_name: config
+            .attribute_name
+            .filter(|name| {
+                let valid = is_valid_attribute_name(name);
+                if !valid {
+                    problems.report(&format!(
+                        "`attributeName` {name:?} is not a valid JSX attribute name, using \
+                         `{DEFAULT_ATTRIBUTE_NAME}`"
+                    ));
+                }
+                valid
+            })
+            .map_or_else(|| prefixed(DEFAULT_ATTRIBUTE_NAME.to_string()), prefixed),
+        component_attribute_name: prefixed(COMPONENT_ATTRIBUTE_NAME.to_string()),
+        format: config
+            .format
+            .filter(|f| !f.is_empty() && value_kind == ValueKind::String),
+        split_attributes: config.split_attributes.unwrap_or(false)
+            && value_kind == ValueKind::String
+            && emit_as == EmitAs::Attribute,
+        include_component_name: config.include_component_name.unwrap_or(false),
+        include_files: compile_globs(problems, "includeFiles", config.include_files),
+        exclude_files: compile_globs(problems, "excludeFiles", config.exclude_files),
+        extensions: config
+            .extensions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|ext| format!(".{}", ext.trim_start_matches('.').to_ascii_lowercase()))
+            .collect(),
+        // `absolutePaths: true` predates `pathStyle` and is kept as a shorthand.
+        path_style: config
+            .path_style
+            .unwrap_or(if config.absolute_paths == Some(true) {
+                PathStyle::Absolute
+            } else {
+                PathStyle::Relative
+            }),
+        mode: config.mode.unwrap_or_default(),
+        overwrite_existing: config.overwrite_existing.unwrap_or(false),
+        include_range: config.include_range.unwrap_or(false),
+        hash_paths: config.hash_paths.unwrap_or(false),
+        emit_manifest: config.emit_manifest.unwrap_or(false),
+        tag_fragments: config.tag_fragments.unwrap_or(false),
+        root_only: config.root_only.unwrap_or(false),
+        component_boundary_only: config.component_boundary_only.unwrap_or(false),
+        styled_components: config.styled_components.unwrap_or(false),
+        strip_extension: config.strip_extension.unwrap_or(false),
+        leading_dot_slash: config.leading_dot_slash.unwrap_or(false),
+        url_encode_path: config.url_encode_path.unwrap_or(false),
+        path_separator: config.path_separator.unwrap_or_default(),
+        case_insensitive_paths: config.case_insensitive_paths.unwrap_or(false),
+        line_offset: config.line_offset.unwrap_or(0),
+        skip_on_spread: config.skip_on_spread.unwrap_or(false),
+        include_module: config.include_module.unwrap_or(false),
+        include_snippet: config.include_snippet.unwrap_or(false),
+        content_hash: config.content_hash.unwrap_or(false),
+        fallback_to_parent_span: config.fallback_to_parent_span.unwrap_or(false),
+        snippet_length: config.snippet_length.unwrap_or(DEFAULT_SNIPPET_LENGTH),
+        library_keys: libraries.keys().cloned().collect(),
+        library_attributes: libraries
+            .into_iter()
+            .filter_map(|(module, library)| {
+                let name = library.attribute?;
+                if !is_valid_attribute_name(&name) {
+                    problems.report(&format!(
+                        "`libraries[{module:?}].attribute` {name:?} is not a valid JSX attribute \
+                         name and is ignored"
+                    ));
+                    return None;
+                }
+                Some((module, name))
+            })
+            .collect(),
+        warn_unused_libraries: config.warn_unused_libraries.unwrap_or(false),
+        value_kind,
+        emit_as,
+        exclude_subtree: config.exclude_subtree.unwrap_or(false),
+        tag_but_skip_children: name_set(config.tag_but_skip_children),
+        prepend_attribute: config.prepend_attribute.unwrap_or(false),
+        spread_precedence: config.spread_precedence,
+        insert_after: config.insert_after.filter(|name| !name.is_empty()),
+        max_depth: config.max_depth,
+        zero_based_lines: config.zero_based_lines.unwrap_or(false),
+        path_prefix: config.path_prefix.unwrap_or_default(),
+        separator,
+        column_separator,
+        only_interactive: config.only_interactive.unwrap_or(false),
+        require_click_handler: config.require_click_handler.unwrap_or(false),
+        quote_style: config.quote_style,
+        include_self_closing: config.include_self_closing.unwrap_or(false),
+        include_prop_count: config.include_prop_count.unwrap_or(false),
+        cwd_candidates: config
+            .cwd_candidates
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|c| !c.is_empty())
+            .collect(),
+        include_element_id: config.include_element_id.unwrap_or(false),
+        opt_out_attribute: non_empty(problems, "optOutAttribute", config.opt_out_attribute)
+            .unwrap_or_else(|| DEFAULT_OPT_OUT_ATTRIBUTE.to_string()),
+        clean_filenames: config.clean_filenames.unwrap_or(false),
+        raw_filename: config.raw_filename.unwrap_or(false),
+        dry_run: config.dry_run.unwrap_or(false),
+        ignore_patterns: compile_ignore_rules(problems, config.ignore_patterns),
+        disable_directive: non_empty(problems, "disableDirective", config.disable_directive)
+            .unwrap_or_else(|| DEFAULT_DISABLE_DIRECTIVE.to_string()),
+        html_only: config.html_only.unwrap_or(false),
+        valid_html_tags_only: config.valid_html_tags_only.unwrap_or(false),
+        custom_elements: config
+            .custom_elements
+            .map(|v| v.into_iter().collect())
+            .unwrap_or_default(),
+        component_detection: config.component_detection.unwrap_or_default(),
+        framework: config.framework.unwrap_or_default(),
+        components_from_imports_only: config.components_from_imports_only.unwrap_or(false),
+        excluded_patterns: compile_regexes(
+            problems,
+            "excludedPatterns",
+            config.excluded_patterns,
+            case_sensitive,
+        ),
+        ignored_member_objects: config
+            .ignored_member_objects
+            .map(|v| v.into_iter().collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// Puts `prefix` after the `data-` of `name` (`data-acme-source`), or in
+/// front of names without one (`acme-source`).
+fn prefix_attribute_name(prefix: &str, name: &str) -> String {
+    match name.strip_prefix("data-") {
+        Some(rest) => format!("data-{prefix}-{rest}"),
+        None => format!("{prefix}-{name}"),
+    }
+}
+
+/// Drops an empty string `value` of `option`, reporting it.
+fn non_empty(problems: &ConfigProblems, option: &str, value: Option<String>) -> Option<String> {
+    value.filter(|value| {
+        if value.is_empty() {
+            problems.report(&format!("`{option}` must not be empty and is ignored"));
+        }
+        !value.is_empty()
+    })
+}
+
+fn compile_globs(
+    problems: &ConfigProblems,
+    option: &str,
+    patterns: Option<Vec<String>>,
+) -> Vec<Pattern> {
+    patterns
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| match Pattern::new(p) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                problems.report(&format!("`{option}` pattern {p:?} is ignored: {e}"));
+                None
+            }
+        })
+        .collect()
+}
+
+/// One line of `ignorePatterns`, see [`compile_ignore_rules`].
+struct IgnoreRule {
+    pattern: Pattern,
+    /// `!pattern`: re-includes what earlier rules ignored.
+    negated: bool,
+    /// `pattern/`: only matches directories.
+    dir_only: bool,
+}
+
+impl IgnoreRule {
+    /// Whether the rule matches `path` itself or one of its directories.
+    fn matches(&self, path: &str, options: MatchOptions) -> bool {
+        let dirs = path.match_indices('/').map(|(i, _)| &path[..i]);
+        let mut candidates = dirs.chain((!self.dir_only).then_some(path));
+        candidates.any(|candidate| self.pattern.matches_with(candidate, options))
+    }
+}
+
+/// Compiles gitignore-style lines: `#` comments, `!` negation, a trailing
+/// `/` for directories, and patterns without an inner `/` matching at any
+/// depth.
+fn compile_ignore_rules(
+    problems: &ConfigProblems,
+    patterns: Option<Vec<String>>,
+) -> Vec<IgnoreRule> {
+    patterns
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|line| {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (negated, rest) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let (dir_only, rest) = match rest.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, rest),
+            };
+            let glob = match rest.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None if rest.contains('/') => rest.to_string(),
+                None => format!("**/{rest}"),
+            };
+            match Pattern::new(&glob) {
+                Ok(pattern) => Some(IgnoreRule {
+                    pattern,
+                    negated,
+                    dir_only,
+                }),
+                Err(e) => {
+                    problems.report(&format!("`ignorePatterns` entry {line:?} is ignored: {e}"));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn compile_regexes(
+    problems: &ConfigProblems,
+    option: &str,
+    patterns: Option<Vec<String>>,
+    case_sensitive: bool,
+) -> Vec<Regex> {
+    patterns
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| {
+            match RegexBuilder::new(p)
+                .case_insensitive(!case_sensitive)
+                .build()
+            {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    problems.report(&format!("`{option}` pattern {p:?} is ignored: {e}"));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+const PLUGIN_NAME: &str = "swc-plugin-react-source-string";
+
+/// Reports a configuration problem that makes the config unusable, if there
+/// is a `HANDLER` to report it to.
+fn config_error(message: &str) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.err(&format!("{PLUGIN_NAME}: {message}")));
+    }
+}
+
+/// Reports a configuration problem that was worked around, if there is a
+/// `HANDLER` to report it to.
+fn config_warning(message: &str) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.warn(&format!("{PLUGIN_NAME}: {message}")));
+    }
+}
+
+/// Accepts names that can be emitted as a plain `JSXAttrName::Ident`:
+/// an ASCII letter, `_` or `$`, followed by letters, digits, `_`, `$` or `-`.
+fn is_valid_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '-'))
+}
+
+/// Expands `{token}` placeholders in `template` using `lookup`.
+/// Unknown tokens are kept verbatim; `{{` and `}}` produce literal braces.
+fn render_template<'a>(template: &str, lookup: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('{') {
+            if let Some(end) = tail.find('}') {
+                let token = &tail[1..end];
+                match lookup(token) {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&tail[..=end]),
+                }
+                rest = &tail[end + 1..];
+                continue;
+            }
+        }
+        out.push_str(&tail[..1]);
+        rest = &tail[1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// First 8 hex chars of the SHA-256 of `text`. For `hashPaths` this is stable
+/// across runs and platforms because the path is already normalized to
+/// forward slashes.
+fn short_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())[..4]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Last segment of a `/`-separated path, ignoring trailing slashes.
+fn basename(path: &str) -> &str {
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(pos) => &trimmed[pos + 1..],
+        None => trimmed,
+    }
+}
+
+const SOURCE_EXTENSIONS: &[&str] = &[".js", ".jsx", ".ts", ".tsx", ".mjs", ".cjs"];
+
+/// Removes one trailing JS/TS extension; `config.json.tsx` becomes `config.json`.
+fn strip_source_extension(path: &str) -> &str {
+    SOURCE_EXTENSIONS
+        .iter()
+        .find_map(|ext| {
+            path.strip_suffix(ext)
+                .filter(|stem| !stem.is_empty() && !stem.ends_with('/'))
+        })
+        .unwrap_or(path)
+}
+
+/// Percent-encodes `%`, space, `#`, `?` and every non-ASCII byte; everything
+/// else, including `/` and `:`, is kept.
+fn percent_encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'%' | b' ' | b'#' | b'?' | 0x80.. => out.push_str(&format!("%{byte:02X}")),
+            _ => out.push(byte as char),
+        }
+    }
+    out
+}
+
+/// Prepends `prefix` to `path` with exactly one separator between them.
+fn join_path_prefix(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        return path.to_string();
+    }
+    let path = path.trim_start_matches(['/', '\\']);
+    if prefix.ends_with(['/', '\\']) {
+        format!("{prefix}{path}")
+    } else {
+        format!("{prefix}/{path}")
+    }
+}
+
+/// `./path` for a relative `path` that doesn't start with `./` or `../` yet.
+fn with_leading_dot_slash(path: String) -> String {
+    let bytes = path.as_bytes();
+    let has_drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    if path.starts_with(['/', '.']) || has_drive || path.is_empty() {
+        return path;
     }
+    format!("./{path}")
+}
+
+/// Drops a webpack loader chain (`babel-loader!./src/App.tsx`) and a
+/// `?query` suffix (`App.vue?vue&type=script`) from `filename`.
+fn clean_filename(filename: &str) -> &str {
+    let filename = filename.rsplit('!').next().unwrap_or(filename);
+    filename.split('?').next().unwrap_or(filename)
 }
 
 fn strip_virtual_prefix(filename: &str) -> &str {
@@ -57,120 +1019,2388 @@ fn strip_virtual_prefix(filename: &str) -> &str {
     filename
 }
 
-fn relative_path(cwd: &str, filename: &str) -> String {
+/// Lexically collapses empty and `.` segments and resolves `..` against the
+/// preceding segment. Leading `..` of a relative path are kept; at the root
+/// of an absolute one they are dropped.
+fn normalize_path(path: &str) -> String {
+    let absolute = path.starts_with('/');
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => match segments.last() {
+                Some(&last) if last != ".." && !has_drive_letter(last) => {
+                    segments.pop();
+                }
+                // `C:/..` stays at the drive root.
+                Some(&last) if last != ".." => {}
+                _ if absolute => {}
+                _ => segments.push(".."),
+            },
+            segment => segments.push(segment),
+        }
+    }
+    let joined = segments.join("/");
+    if absolute {
+        format!("/{joined}")
+    } else {
+        joined
+    }
+}
+
+/// Strips `cwd` from `filename`, keeping `filename` if it isn't inside `cwd`.
+fn relative_path(cwd: &str, filename: &str, case_insensitive: bool) -> String {
+    strip_cwd(cwd, filename, case_insensitive).unwrap_or_else(|| filename.replace('\\', "/"))
+}
+
+/// `filename` relative to the directory `cwd`, or `None` if it isn't inside
+/// it. A leading Windows drive letter (`C:/`) is always compared
+/// case-insensitively; the rest only with `case_insensitive`.
+fn strip_cwd(cwd: &str, filename: &str, case_insensitive: bool) -> Option<String> {
+    // Canonical like the filename, so `C:\repo\` and `/repo/./` match too.
+    let cwd = normalize_path(&cwd.replace('\\', "/"));
     let cwd = cwd.trim_end_matches('/');
     let filename = filename.replace('\\', "/");
-    if let Some(stripped) = filename.strip_prefix(cwd) {
-        stripped.trim_start_matches('/').to_string()
+    let stripped = strip_path_prefix(&filename, cwd, case_insensitive)?;
+    // `/repo/app` doesn't contain `/repo/application/x.tsx`.
+    if !stripped.is_empty() && !stripped.starts_with('/') {
+        return None;
+    }
+    Some(stripped.trim_start_matches('/').to_string())
+}
+
+fn strip_path_prefix<'a>(path: &'a str, prefix: &str, case_insensitive: bool) -> Option<&'a str> {
+    if path.len() < prefix.len() || !path.is_char_boundary(prefix.len()) {
+        return None;
+    }
+    let (head, rest) = path.split_at(prefix.len());
+    let matches = if case_insensitive {
+        head.eq_ignore_ascii_case(prefix)
+    } else if has_drive_letter(head) && has_drive_letter(prefix) {
+        head[..1].eq_ignore_ascii_case(&prefix[..1]) && head[1..] == prefix[1..]
     } else {
-        filename
+        head == prefix
+    };
+    matches.then_some(rest)
+}
+
+fn has_drive_letter(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Somewhere source attributes can be injected: the attributes of a JSX
+/// opening element, or the props object of a compiled `jsx()` call.
+trait AttrTarget: Clone {
+    fn has_attr(&self, name: &str) -> bool;
+    fn has_spread(&self) -> bool;
+    /// Removes plain attributes named in `names`, returning how many were
+    /// removed; spreads are never touched.
+    fn remove_attrs(&mut self, names: &[String]) -> usize;
+    fn push_attr(&mut self, name: &str, value: String, quote: Option<QuoteStyle>);
+    fn push_expr_attr(&mut self, name: &str, value: Box<Expr>, quote: Option<QuoteStyle>);
+    /// Pushes an attribute without a value (`true`).
+    fn push_flag_attr(&mut self, name: &str, quote: Option<QuoteStyle>);
+    fn attr_count(&self) -> usize;
+    /// Props the element was written with, for `includePropCount`.
+    fn prop_count(&self) -> usize {
+        self.attr_count()
     }
+    /// Index of the first plain attribute named `name`.
+    fn attr_position(&self, name: &str) -> Option<usize>;
+    /// Indexes of the first and the last spread, if there is one.
+    fn spread_bounds(&self) -> Option<(usize, usize)>;
+    /// Moves the attributes from index `from` onwards to `index`.
+    fn move_to(&mut self, from: usize, index: usize);
 }
 
-struct ReactSourceStringVisitor {
-    excluded: HashSet<String>,
-    source_map: swc_core::plugin::proxies::PluginSourceMapProxy,
-    cwd: Option<String>,
+fn str_lit(value: String, quote: Option<QuoteStyle>) -> Str {
+    Str {
+        span: DUMMY_SP,
+        raw: quote.and_then(|q| q.quote(&value)).map(Into::into),
+        value: value.into(),
+    }
 }
 
-impl ReactSourceStringVisitor {
-    fn new(
-        config: ParsedConfig,
-        source_map: swc_core::plugin::proxies::PluginSourceMapProxy,
-        metadata: &TransformPluginProgramMetadata,
-    ) -> Self {
-        let cwd = config.root.filter(|s| !s.is_empty()).or_else(|| {
-            metadata
-                .get_experimental_context("cwd")
-                .filter(|s| !s.is_empty())
-        });
-        Self {
-            excluded: config.excluded,
-            source_map,
-            cwd,
-        }
+/// [`str_lit`] for a JSX attribute. JSX strings have no backslash escapes,
+/// so values the code generator would escape get a `raw` using HTML
+/// entities instead, which JSX decodes.
+fn jsx_str_lit(value: String, quote: Option<QuoteStyle>) -> Str {
+    let mut lit = str_lit(value, quote);
+    let value = lit.value.as_str();
+    let needs_entities =
+        value.contains(['\\', '&', '\n', '\r']) || (value.contains('"') && value.contains('\''));
+    if lit.raw.is_none() && needs_entities {
+        let escaped = value.replace('&', "&amp;").replace('"', "&quot;");
+        lit.raw = Some(format!("\"{escaped}\"").into());
     }
+    lit
+}
 
-    fn jsx_element_name_str(name: &JSXElementName) -> Option<String> {
-        match name {
-            JSXElementName::Ident(i) => Some(i.sym.to_string()),
-            JSXElementName::JSXMemberExpr(m) => Some(m.prop.sym.to_string()),
-            JSXElementName::JSXNamespacedName(n) => Some(n.name.sym.to_string()),
-            #[cfg(swc_ast_unknown)]
-            _ => panic!("unknown JSXElementName"),
-        }
+fn jsx_attr_name_is(attr: &JSXAttrOrSpread, name: &str) -> bool {
+    match attr {
+        JSXAttrOrSpread::JSXAttr(JSXAttr {
+            name: JSXAttrName::Ident(i),
+            ..
+        }) => i.sym == name,
+        _ => false,
     }
+}
 
-    fn has_data_source(attrs: &[JSXAttrOrSpread]) -> bool {
-        attrs.iter().any(|a| {
-            if let JSXAttrOrSpread::JSXAttr(attr) = a {
-                if let JSXAttrName::Ident(i) = &attr.name {
-                    return i.sym == "data-source";
-                }
-            }
-            false
-        })
+impl AttrTarget for Vec<JSXAttrOrSpread> {
+    fn has_attr(&self, name: &str) -> bool {
+        self.iter().any(|a| jsx_attr_name_is(a, name))
     }
 
-    fn make_data_source_attr(&self, span: swc_core::common::Span) -> Option<JSXAttrOrSpread> {
-        if span.is_dummy() {
-            return None;
-        }
-        let loc = self.source_map.lookup_char_pos(span.lo);
-        let line = loc.line;
-        let raw_filename = loc.file.name.to_string().replace('\\', "/");
-        let filename = strip_virtual_prefix(&raw_filename);
-        let relative = self
-            .cwd
-            .as_ref()
-            .map(|cwd| relative_path(cwd, filename))
-            .unwrap_or_else(|| filename.to_string());
-        let source_value = format!("{relative}:{line}");
-        let value = Str {
+    fn has_spread(&self) -> bool {
+        self.iter()
+            .any(|a| matches!(a, JSXAttrOrSpread::SpreadElement(_)))
+    }
+
+    fn remove_attrs(&mut self, names: &[String]) -> usize {
+        let len = self.len();
+        self.retain(|a| !names.iter().any(|name| jsx_attr_name_is(a, name)));
+        len - self.len()
+    }
+
+    fn push_attr(&mut self, name: &str, value: String, quote: Option<QuoteStyle>) {
+        self.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
             span: DUMMY_SP,
-            value: source_value.into(),
-            raw: None,
-        };
-        let attr = JSXAttr {
+            name: JSXAttrName::Ident(
+                Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty()).into(),
+            ),
+            value: Some(JSXAttrValue::Lit(Lit::Str(jsx_str_lit(value, quote)))),
+        }));
+    }
+
+    fn push_flag_attr(&mut self, name: &str, _quote: Option<QuoteStyle>) {
+        self.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
             span: DUMMY_SP,
             name: JSXAttrName::Ident(
-                Ident::new("data-source".into(), DUMMY_SP, SyntaxContext::empty()).into(),
+                Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty()).into(),
             ),
-            value: Some(JSXAttrValue::Lit(Lit::Str(value))),
-        };
-        Some(JSXAttrOrSpread::JSXAttr(attr))
+            value: None,
+        }));
+    }
+
+    fn attr_count(&self) -> usize {
+        self.len()
+    }
+
+    fn attr_position(&self, name: &str) -> Option<usize> {
+        self.iter().position(|a| jsx_attr_name_is(a, name))
+    }
+
+    fn spread_bounds(&self) -> Option<(usize, usize)> {
+        let is_spread = |a: &JSXAttrOrSpread| matches!(a, JSXAttrOrSpread::SpreadElement(_));
+        let first = self.iter().position(is_spread)?;
+        let last = self.iter().rposition(is_spread)?;
+        Some((first, last))
+    }
+
+    fn move_to(&mut self, from: usize, index: usize) {
+        let len = self.len();
+        self[index..].rotate_right(len - from);
+    }
+
+    fn push_expr_attr(&mut self, name: &str, value: Box<Expr>, _quote: Option<QuoteStyle>) {
+        self.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+            span: DUMMY_SP,
+            name: JSXAttrName::Ident(
+                Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty()).into(),
+            ),
+            value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                span: DUMMY_SP,
+                expr: JSXExpr::Expr(value),
+            })),
+        }));
     }
 }
 
-impl VisitMut for ReactSourceStringVisitor {
-    fn visit_mut_jsx_opening_element(&mut self, el: &mut JSXOpeningElement) {
-        el.visit_mut_children_with(self);
+fn prop_key_is(prop: &PropOrSpread, name: &str) -> bool {
+    let PropOrSpread::Prop(prop) = prop else {
+        return false;
+    };
+    match &**prop {
+        Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(s),
+            ..
+        }) => s.value == name,
+        Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(i),
+            ..
+        }) => i.sym == name,
+        _ => false,
+    }
+}
 
-        let element_name = match Self::jsx_element_name_str(&el.name) {
-            Some(n) => n,
-            None => return,
-        };
+impl AttrTarget for ObjectLit {
+    fn has_attr(&self, name: &str) -> bool {
+        self.props.iter().any(|p| prop_key_is(p, name))
+    }
 
-        if self.excluded.contains(&element_name.to_lowercase()) {
-            return;
-        }
+    fn has_spread(&self) -> bool {
+        self.props
+            .iter()
+            .any(|p| matches!(p, PropOrSpread::Spread(_)))
+    }
 
-        if Self::has_data_source(&el.attrs) {
-            return;
-        }
+    fn remove_attrs(&mut self, names: &[String]) -> usize {
+        let len = self.props.len();
+        self.props
+            .retain(|p| !names.iter().any(|name| prop_key_is(p, name)));
+        len - self.props.len()
+    }
 
-        if let Some(attr) = self.make_data_source_attr(el.span) {
-            el.attrs.push(attr);
-        }
+    fn push_attr(&mut self, name: &str, value: String, quote: Option<QuoteStyle>) {
+        self.props
+            .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Str(str_lit(name.to_string(), quote)),
+                value: Box::new(Expr::Lit(Lit::Str(str_lit(value, quote)))),
+            }))));
+    }
+
+    fn push_flag_attr(&mut self, name: &str, quote: Option<QuoteStyle>) {
+        let value = Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value: true,
+        }));
+        self.push_expr_attr(name, Box::new(value), quote);
+    }
+
+    fn attr_count(&self) -> usize {
+        self.props.len()
+    }
+
+    /// `children` is written as JSX children, not as a prop.
+    fn prop_count(&self) -> usize {
+        self.props.iter().filter(|p| !is_children_prop(p)).count()
+    }
+
+    fn attr_position(&self, name: &str) -> Option<usize> {
+        self.props.iter().position(|p| prop_key_is(p, name))
+    }
+
+    fn spread_bounds(&self) -> Option<(usize, usize)> {
+        let is_spread = |p: &PropOrSpread| matches!(p, PropOrSpread::Spread(_));
+        let first = self.props.iter().position(is_spread)?;
+        let last = self.props.iter().rposition(is_spread)?;
+        Some((first, last))
+    }
+
+    fn move_to(&mut self, from: usize, index: usize) {
+        let len = self.props.len();
+        self.props[index..].rotate_right(len - from);
+    }
+
+    fn push_expr_attr(&mut self, name: &str, value: Box<Expr>, quote: Option<QuoteStyle>) {
+        self.props
+            .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Str(str_lit(name.to_string(), quote)),
+                value,
+            }))));
     }
 }
 
-#[plugin_transform]
-pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-    let config = parse_config(&metadata);
-    let source_map = metadata.source_map.clone();
-    let mut visitor = ReactSourceStringVisitor::new(config, source_map, &metadata);
-    let mut program = program;
-    program.visit_mut_with(&mut visitor);
-    program
+/// Calls that create elements at runtime.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ElementFactory {
+    /// `jsx`, `jsxs`, `jsxDEV` imported from a `*/jsx-runtime` or
+    /// `*/jsx-dev-runtime` module under any name, or called through a
+    /// namespace import of one or CommonJS's `_jsxRuntime`.
+    JsxRuntime,
+    /// `React.createElement`, a `createElement` imported from React or
+    /// Preact, or the factory named by a `@jsx` pragma (Preact's `h`).
+    CreateElement,
+}
+
+/// `pragma` is the factory named by the file's `@jsx` pragma, if any, and
+/// `imports` the file's import bindings.
+fn element_factory(
+    callee: &Callee,
+    pragma: Option<&str>,
+    imports: &HashMap<String, ImportBinding>,
+) -> Option<ElementFactory> {
+    let Callee::Expr(expr) = callee else {
+        return None;
+    };
+    let mut expr = &**expr;
+    // CommonJS output calls `(0, _jsxRuntime.jsx)(...)`.
+    while let Expr::Paren(ParenExpr { expr: inner, .. }) = expr {
+        expr = match &**inner {
+            Expr::Seq(seq) => seq.exprs.last().map(|e| &**e).unwrap_or(inner),
+            other => other,
+        };
+    }
+    if pragma.is_some_and(|pragma| element_type_name(expr).as_deref() == Some(pragma)) {
+        return Some(ElementFactory::CreateElement);
+    }
+    match expr {
+        Expr::Ident(i)
+            if imports.get(&*i.sym).is_some_and(|binding| {
+                is_jsx_runtime_module(&binding.source)
+                    && binding
+                        .imported
+                        .as_deref()
+                        .is_some_and(is_jsx_runtime_export)
+            }) =>
+        {
+            Some(ElementFactory::JsxRuntime)
+        }
+        // Only `createElement` imported from React or Preact, under any name.
+        Expr::Ident(i)
+            if imports.get(&*i.sym).is_some_and(|binding| {
+                is_react_module(&binding.source)
+                    && binding.imported.as_deref() == Some("createElement")
+            }) =>
+        {
+            Some(ElementFactory::CreateElement)
+        }
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if is_jsx_runtime_export(&prop.sym) && is_jsx_runtime_object(obj, imports) => {
+            Some(ElementFactory::JsxRuntime)
+        }
+        // Only `React.createElement`-like members, so that e.g.
+        // `document.createElement("div")` is left alone.
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if prop.sym == "createElement" && is_react_object(obj, imports) => {
+            Some(ElementFactory::CreateElement)
+        }
+        _ => None,
+    }
+}
+
+/// Whether `prop` is the `children` key of automatic-runtime props.
+fn is_children_prop(prop: &PropOrSpread) -> bool {
+    let PropOrSpread::Prop(prop) = prop else {
+        return false;
+    };
+    match &**prop {
+        Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(name),
+            ..
+        }) => name.sym == "children",
+        Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(name),
+            ..
+        }) => name.value == "children",
+        Prop::Shorthand(name) => name.sym == "children",
+        _ => false,
+    }
+}
+
+/// The factory named by a classic-runtime `/** @jsx h */` pragma in the
+/// comments leading `program`.
+fn jsx_pragma(program: &Program, comments: &impl Comments) -> Option<String> {
+    let span = program.span();
+    if span.is_dummy() {
+        return None;
+    }
+    comments.get_leading(span.lo)?.iter().find_map(|comment| {
+        let mut words = comment
+            .text
+            .split_whitespace()
+            .map(|word| word.trim_start_matches('*'));
+        words.find(|word| *word == "@jsx")?;
+        words
+            .next()
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Whether a comment leading `program`, or its first statement after any
+/// `"use client"`-style directives, contains `directive` as a word, as in
+/// `/* @no-react-source */`.
+fn has_disable_directive(program: &Program, comments: &impl Comments, directive: &str) -> bool {
+    let span = program.span();
+    if span.is_dummy() {
+        return false;
+    }
+    let is_directive = |stmt: &Stmt| matches!(stmt, Stmt::Expr(ExprStmt { expr, .. }) if matches!(&**expr, Expr::Lit(Lit::Str(_))));
+    let first_statement = match program {
+        Program::Module(module) => module
+            .body
+            .iter()
+            .find(|item| !matches!(item, ModuleItem::Stmt(stmt) if is_directive(stmt)))
+            .map(|item| item.span()),
+        Program::Script(script) => script
+            .body
+            .iter()
+            .find(|stmt| !is_directive(stmt))
+            .map(|stmt| stmt.span()),
+        #[cfg(swc_ast_unknown)]
+        _ => None,
+    };
+    let has_directive = |pos: BytePos| {
+        comments.get_leading(pos).is_some_and(|leading| {
+            leading.iter().any(|comment| {
+                comment
+                    .text
+                    .split_whitespace()
+                    .any(|word| word.trim_start_matches('*') == directive)
+            })
+        })
+    };
+    has_directive(span.lo) || first_statement.is_some_and(|stmt| has_directive(stmt.lo))
+}
+
+/// `react/jsx-runtime`, `preact/jsx-dev-runtime` and the like.
+fn is_jsx_runtime_module(source: &str) -> bool {
+    source.ends_with("/jsx-runtime") || source.ends_with("/jsx-dev-runtime")
+}
+
+fn is_jsx_runtime_export(name: &str) -> bool {
+    matches!(name, "jsx" | "jsxs" | "jsxDEV")
+}
+
+/// `_jsxRuntime` (CommonJS interop) or a namespace import of a runtime module.
+fn is_jsx_runtime_object(expr: &Expr, imports: &HashMap<String, ImportBinding>) -> bool {
+    let Expr::Ident(i) = expr else {
+        return false;
+    };
+    matches!(&*i.sym, "_jsxRuntime" | "_jsxDevRuntime")
+        || imports.get(&*i.sym).is_some_and(|binding| {
+            is_jsx_runtime_module(&binding.source) && binding.imported.is_none()
+        })
+}
+
+/// Modules whose `createElement` is recognized.
+fn is_react_module(source: &str) -> bool {
+    matches!(source, "react" | "preact")
+}
+
+/// `React`, `_react` (CommonJS interop), `_react.default`, or the local name
+/// of a default or namespace import from React or Preact.
+fn is_react_object(expr: &Expr, imports: &HashMap<String, ImportBinding>) -> bool {
+    match expr {
+        Expr::Ident(i) => {
+            matches!(&*i.sym, "React" | "_react")
+                || imports.get(&*i.sym).is_some_and(|binding| {
+                    is_react_module(&binding.source)
+                        && binding
+                            .imported
+                            .as_deref()
+                            .is_none_or(|name| name == "default")
+                })
+        }
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => prop.sym == "default" && is_react_object(obj, imports),
+        _ => false,
+    }
+}
+
+/// Element name of a compiled element type: `"div"`, `Button` or the full
+/// dotted path of a member expression (`Menu.Item`).
+fn element_type_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+        Expr::Ident(i) => Some(i.sym.to_string()),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => Some(format!("{}.{}", element_type_name(obj)?, prop.sym)),
+        _ => None,
+    }
+}
+
+/// Element a `styled-components` tag renders and whether it is a host
+/// element: `styled.div` or `styled(Button)`. Tags that already call a
+/// method, like `styled.div.attrs(...)`, are left alone.
+fn styled_target(tag: &Expr) -> Option<(String, bool)> {
+    let is_styled = |expr: &Expr| matches!(expr, Expr::Ident(i) if i.sym == "styled");
+    match tag {
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if is_styled(obj) => Some((prop.sym.to_string(), true)),
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) if is_styled(callee) => {
+            let arg = args.first().filter(|arg| arg.spread.is_none())?;
+            let intrinsic = matches!(&*arg.expr, Expr::Lit(Lit::Str(_)));
+            Some((element_type_name(&arg.expr)?, intrinsic))
+        }
+        _ => None,
+    }
+}
+
+/// Standard HTML and SVG element names, lowercased and sorted for binary search.
+static HTML_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "animate",
+    "animatemotion",
+    "animatetransform",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "circle",
+    "cite",
+    "clippath",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "defs",
+    "del",
+    "desc",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "ellipse",
+    "em",
+    "embed",
+    "feblend",
+    "fecolormatrix",
+    "fecomponenttransfer",
+    "fecomposite",
+    "feconvolvematrix",
+    "fediffuselighting",
+    "fedisplacementmap",
+    "fedistantlight",
+    "fedropshadow",
+    "feflood",
+    "fefunca",
+    "fefuncb",
+    "fefuncg",
+    "fefuncr",
+    "fegaussianblur",
+    "feimage",
+    "femerge",
+    "femergenode",
+    "femorphology",
+    "feoffset",
+    "fepointlight",
+    "fespecularlighting",
+    "fespotlight",
+    "fetile",
+    "feturbulence",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "filter",
+    "footer",
+    "foreignobject",
+    "form",
+    "g",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "image",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "line",
+    "lineargradient",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "marker",
+    "mask",
+    "menu",
+    "meta",
+    "metadata",
+    "meter",
+    "mpath",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "path",
+    "pattern",
+    "picture",
+    "polygon",
+    "polyline",
+    "pre",
+    "progress",
+    "q",
+    "radialgradient",
+    "rect",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "set",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "stop",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "svg",
+    "switch",
+    "symbol",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "text",
+    "textarea",
+    "textpath",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "tspan",
+    "u",
+    "ul",
+    "use",
+    "var",
+    "video",
+    "view",
+    "wbr",
+];
+
+fn is_known_html_tag(name: &str) -> bool {
+    HTML_TAGS
+        .binary_search(&name.to_ascii_lowercase().as_str())
+        .is_ok()
+}
+
+/// Host elements tagged by `onlyInteractive` even without a click handler.
+const INTERACTIVE_TAGS: &[&str] = &["a", "button", "input", "select", "textarea"];
+
+/// Whether `target` has an explicit `onClick` / `onClickCapture`.
+fn has_click_handler(target: &impl AttrTarget) -> bool {
+    target.has_attr("onClick") || target.has_attr("onClickCapture")
+}
+
+/// `Fragment`, `React.Fragment` or the compiled `_Fragment` import.
+fn is_fragment(element_name: &str) -> bool {
+    let last = element_name.rsplit('.').next().unwrap_or(element_name);
+    last.trim_start_matches('_') == "Fragment"
+}
+
+/// `ident` if it is named like a component, i.e. starts with an uppercase
+/// letter.
+fn component_name(ident: &Ident) -> Option<String> {
+    ident
+        .sym
+        .starts_with(|c: char| c.is_ascii_uppercase())
+        .then(|| ident.sym.to_string())
+}
+
+/// Spans of the elements `expr` may evaluate to, as passed to `tag`: the
+/// `JSXElement`s or factory calls. Both branches of `cond ? <A /> : <B />`
+/// and of `a && <A />` count.
+fn returned_roots(expr: &Expr) -> Vec<Span> {
+    let mut roots = Vec::new();
+    push_returned_roots(expr, &mut roots);
+    roots
+}
+
+fn push_returned_roots(expr: &Expr, roots: &mut Vec<Span>) {
+    match expr {
+        Expr::Paren(paren) => push_returned_roots(&paren.expr, roots),
+        Expr::JSXElement(el) => roots.push(el.span),
+        Expr::Call(call) => roots.push(call.span),
+        Expr::Cond(cond) => {
+            push_returned_roots(&cond.cons, roots);
+            push_returned_roots(&cond.alt, roots);
+        }
+        Expr::Bin(bin)
+            if matches!(
+                bin.op,
+                BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing
+            ) =>
+        {
+            push_returned_roots(&bin.left, roots);
+            push_returned_roots(&bin.right, roots);
+        }
+        Expr::Seq(seq) => {
+            if let Some(last) = seq.exprs.last() {
+                push_returned_roots(last, roots);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `expr` defines a component when assigned to a capitalised name:
+/// a function, or one wrapped in calls such as `memo(() => …)` and
+/// `forwardRef((props, ref) => …)`.
+fn is_component_function(expr: &Expr) -> bool {
+    match expr {
+        Expr::Arrow(_) | Expr::Fn(_) => true,
+        Expr::Paren(paren) => is_component_function(&paren.expr),
+        Expr::Call(call) => call
+            .args
+            .first()
+            .is_some_and(|arg| arg.spread.is_none() && is_component_function(&arg.expr)),
+        _ => false,
+    }
+}
+
+/// Names of the attributes injected for a source location.
+fn source_attr_names(base: &str, config: &ParsedConfig) -> Vec<String> {
+    if !config.split_attributes {
+        return vec![base.to_string()];
+    }
+    if config.position_kind == PositionKind::Offset {
+        let mut names = vec![format!("{base}-file"), format!("{base}-offset")];
+        if config.content_hash {
+            names.push(format!("{base}-hash"));
+        }
+        return names;
+    }
+    let mut names = vec![format!("{base}-file"), format!("{base}-line")];
+    if config.include_column {
+        names.push(format!("{base}-column"));
+    }
+    if config.content_hash {
+        names.push(format!("{base}-hash"));
+    }
+    names
+}
+
+/// Resolved paths of one source file, see [`ReactSourceStringVisitor::file_paths`].
+struct FilePaths {
+    /// Matched by `includeFiles` / `excludeFiles` and listed in the manifest.
+    source: String,
+    /// Written to the attribute.
+    emitted: String,
+}
+
+/// Everything learned while visiting one program. `transform` builds a new
+/// one per call, so nothing carries over to the next file.
+struct ReactSourceStringVisitor<'a, S: ?Sized + SourceMapper> {
+    config: &'a ParsedConfig,
+    source_map: &'a S,
+    cwd: Option<String>,
+    /// Span of the innermost `JSXElement` being visited, covering the closing tag.
+    element_span: Option<Span>,
+    /// Span of the innermost enclosing element that has a real one, for
+    /// `fallbackToParentSpan`.
+    real_span: Option<Span>,
+    /// Whether the element being tagged borrowed `real_span`.
+    approximate: bool,
+    manifest: Vec<ManifestEntry>,
+    /// Number of enclosing (non-fragment) elements.
+    depth: usize,
+    /// Number of enclosing excluded elements, with `excludeSubtree`.
+    excluded_depth: usize,
+    /// Factory named by the file's `@jsx` pragma.
+    pragma: Option<String>,
+    /// Next `includeElementId` value.
+    next_element_id: usize,
+    /// Elements that received the source attributes.
+    tagged_count: usize,
+    /// Name of the component the innermost enclosing function declares, with
+    /// `componentBoundaryOnly`.
+    component: Option<String>,
+    /// Component name for the next function visited, set by its declaration.
+    next_component: Option<String>,
+    /// Spans of the elements the current `return` (or arrow body) yields.
+    boundary_roots: Vec<Span>,
+    /// Name of the class component whose body is being visited, for its
+    /// `render` method.
+    class_component: Option<String>,
+    path_cache: RefCell<HashMap<Lrc<FileName>, Rc<FilePaths>>>,
+    source_attr_names: Vec<String>,
+    /// Local binding -> where it is imported from, for `includeModule`,
+    /// `libraries` and aliased exclusions.
+    imports: HashMap<String, ImportBinding>,
+    /// `libraries` entries imported from anywhere in the program.
+    used_libraries: HashSet<String>,
+    comments: &'a dyn Comments,
+    /// Where the `emitAs: "comment"` comment for the element being tagged
+    /// goes; `None` where there's nowhere valid to print one.
+    comment_slot: Option<CommentSlot>,
+    /// `emitAs: "comment"` text for the JSX element whose opening tag was
+    /// just visited, to be put in its children.
+    child_comment: Option<String>,
+}
+
+/// Where an `emitAs: "comment"` comment goes.
+#[derive(Clone, Copy)]
+enum CommentSlot {
+    /// A leading comment at this position: the first attribute of a JSX
+    /// element, or the callee of a call.
+    Before(BytePos),
+    /// `{/* … */}` as the first child of the JSX element.
+    FirstChild,
+}
+
+/// Stands in for the element with `emitAs: "comment"`: answers like the
+/// element would, but what is pushed only ends up in the comment.
+#[derive(Clone)]
+struct CommentAttrs<T> {
+    element: T,
+    /// Name and value (`None` for a flag) of each pushed attribute.
+    pushed: Vec<(String, Option<String>)>,
+}
+
+impl<T: AttrTarget> AttrTarget for CommentAttrs<T> {
+    fn has_attr(&self, name: &str) -> bool {
+        self.element.has_attr(name) || self.pushed.iter().any(|(pushed, _)| pushed == name)
+    }
+
+    fn has_spread(&self) -> bool {
+        self.element.has_spread()
+    }
+
+    fn remove_attrs(&mut self, names: &[String]) -> usize {
+        self.element.remove_attrs(names)
+    }
+
+    fn push_attr(&mut self, name: &str, value: String, _quote: Option<QuoteStyle>) {
+        self.pushed.push((name.to_string(), Some(value)));
+    }
+
+    // `valueKind: "object"` is turned off with `emitAs: "comment"`.
+    fn push_expr_attr(&mut self, _name: &str, _value: Box<Expr>, _quote: Option<QuoteStyle>) {}
+
+    fn push_flag_attr(&mut self, name: &str, _quote: Option<QuoteStyle>) {
+        self.pushed.push((name.to_string(), None));
+    }
+
+    fn attr_count(&self) -> usize {
+        self.element.attr_count() + self.pushed.len()
+    }
+
+    fn prop_count(&self) -> usize {
+        self.element.prop_count()
+    }
+
+    fn attr_position(&self, name: &str) -> Option<usize> {
+        self.element.attr_position(name)
+    }
+
+    fn spread_bounds(&self) -> Option<(usize, usize)> {
+        self.element.spread_bounds()
+    }
+
+    // The comment has no order to keep.
+    fn move_to(&mut self, _from: usize, _index: usize) {}
+}
+
+/// An `import`ed binding.
+struct ImportBinding {
+    /// Module the binding is imported from.
+    source: String,
+    /// Exported name the binding refers to (`default` for a default import);
+    /// `None` for a namespace import.
+    imported: Option<String>,
+}
+
+/// One injected location, collected when `emitManifest` is enabled.
+#[derive(Serialize)]
+struct ManifestEntry {
+    file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+    line: usize,
+    element: String,
+}
+
+const MANIFEST_MARKER: &str = "@react-source-manifest";
+
+const DRY_RUN_MARKER: &str = "@react-source-dry-run";
+
+/// Attaches the manifest as a leading block comment at the start of the
+/// program: `/* @react-source-manifest [{"file":…,"line":…,"element":…}] */`.
+fn emit_manifest(program: &Program, comments: &impl Comments, entries: &[ManifestEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+    emit_json_comment(program, comments, MANIFEST_MARKER, entries);
+}
+
+/// `dryRun` summary, see [`emit_dry_run`].
+#[derive(Serialize)]
+struct DryRunSummary {
+    /// Elements that would have been tagged.
+    count: usize,
+}
+
+/// Attaches `/* @react-source-dry-run {"count":…} */` at the start of the
+/// program.
+fn emit_dry_run(program: &Program, comments: &impl Comments, count: usize) {
+    emit_json_comment(program, comments, DRY_RUN_MARKER, &DryRunSummary { count });
+}
+
+/// Block comment with `text`, for `emitAs: "comment"`.
+fn location_comment(text: String) -> Comment {
+    Comment {
+        kind: CommentKind::Block,
+        span: DUMMY_SP,
+        text: text.into(),
+    }
+}
+
+/// Whether `comments` has one with exactly `text`, left by an earlier run.
+fn has_comment(comments: Option<Vec<Comment>>, text: &str) -> bool {
+    comments.is_some_and(|comments| comments.iter().any(|comment| *comment.text == *text))
+}
+
+fn emit_json_comment(
+    program: &Program,
+    comments: &impl Comments,
+    marker: &str,
+    value: &(impl Serialize + ?Sized),
+) {
+    let span = program.span();
+    if span.is_dummy() {
+        return;
+    }
+    // Running again over our own output must not stack up comments.
+    let emitted = comments.get_leading(span.lo).is_some_and(|leading| {
+        leading
+            .iter()
+            .any(|comment| comment.text.trim_start().starts_with(marker))
+    });
+    if emitted {
+        return;
+    }
+    let json = match serde_json::to_string(value) {
+        Ok(json) => json,
+        Err(_) => return,
+    };
+    // `*/` would end the comment early; `\/` is an equivalent JSON escape.
+    let json = json.replace("*/", "*\\/");
+    comments.add_leading(
+        span.lo,
+        Comment {
+            kind: CommentKind::Block,
+            span: DUMMY_SP,
+            text: format!(" {marker} {json} ").into(),
+        },
+    );
+}
+
+impl<'a, S: ?Sized + SourceMapper> ReactSourceStringVisitor<'a, S> {
+    fn new(config: &'a ParsedConfig, source_map: &'a S, comments: &'a dyn Comments) -> Self {
+        let cwd = config.root.clone().filter(|s| !s.is_empty());
+        Self {
+            source_attr_names: source_attr_names(&config.attribute_name, config),
+            config,
+            source_map,
+            cwd,
+            element_span: None,
+            real_span: None,
+            approximate: false,
+            manifest: Vec::new(),
+            depth: 0,
+            excluded_depth: 0,
+            pragma: None,
+            next_element_id: 0,
+            tagged_count: 0,
+            component: None,
+            next_component: None,
+            boundary_roots: Vec::new(),
+            class_component: None,
+            imports: HashMap::new(),
+            used_libraries: HashSet::new(),
+            comments,
+            comment_slot: None,
+            child_comment: None,
+            path_cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Name used for exclusion matching and `data-component`: the identifier,
+    /// the full dotted path of a member expression (`Menu.Item.Icon`, so that
+    /// it doesn't collide with another `Icon`), or the full `ns:name` of a
+    /// namespaced element (`svg:rect` is not a `rect`).
+    fn jsx_element_name_str(name: &JSXElementName) -> Option<Cow<'_, str>> {
+        match name {
+            JSXElementName::Ident(i) => Some(Cow::Borrowed(&i.sym)),
+            JSXElementName::JSXMemberExpr(m) => Some(Cow::Owned(Self::jsx_member_expr_str(m))),
+            JSXElementName::JSXNamespacedName(n) => {
+                Some(Cow::Owned(format!("{}:{}", n.ns.sym, n.name.sym)))
+            }
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unknown JSXElementName"),
+        }
+    }
+
+    /// Whether `name` is a host element (`div`, `my-widget`, `svg:rect`) rather
+    /// than a component, per `componentDetection` and `framework`, or by
+    /// whether it is imported with `componentsFromImportsOnly`. Member
+    /// expressions are always components.
+    fn is_intrinsic(&self, name: &JSXElementName) -> bool {
+        if self.config.components_from_imports_only {
+            return match name {
+                JSXElementName::Ident(i) => !self.imports.contains_key(&*i.sym),
+                JSXElementName::JSXNamespacedName(_) => true,
+                _ => false,
+            };
+        }
+        let detection = self.config.component_detection;
+        match name {
+            // Vue resolves kebab-case names to components.
+            JSXElementName::Ident(i)
+                if self.config.framework == Framework::Vue
+                    && i.sym.contains('-')
+                    && !is_known_html_tag(&i.sym)
+                    && !self.config.custom_elements.contains(&*i.sym) =>
+            {
+                false
+            }
+            JSXElementName::Ident(i) => match detection {
+                ComponentDetection::HasUpper => !i.sym.chars().any(char::is_uppercase),
+                ComponentDetection::FirstUpper => !i.sym.starts_with(char::is_uppercase),
+                ComponentDetection::NotHtmlTag => {
+                    i.sym.starts_with(|c: char| c.is_ascii_lowercase())
+                        && (is_known_html_tag(&i.sym)
+                            || self.config.custom_elements.contains(&*i.sym))
+                }
+            },
+            // `svg:rect` is always a host element to React.
+            JSXElementName::JSXNamespacedName(_) if detection != ComponentDetection::HasUpper => {
+                true
+            }
+            JSXElementName::JSXNamespacedName(n) => {
+                !n.ns.sym.chars().any(char::is_uppercase)
+                    && !n.name.sym.chars().any(char::is_uppercase)
+            }
+            JSXElementName::JSXMemberExpr(_) => false,
+            #[cfg(swc_ast_unknown)]
+            _ => false,
+        }
+    }
+
+    fn jsx_member_expr_str(m: &JSXMemberExpr) -> String {
+        let obj = match &m.obj {
+            JSXObject::Ident(i) => i.sym.to_string(),
+            JSXObject::JSXMemberExpr(inner) => Self::jsx_member_expr_str(inner),
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unknown JSXObject"),
+        };
+        format!("{obj}.{}", m.prop.sym)
+    }
+
+    /// Paths for the file `name`, computed once per file.
+    fn file_paths(&self, name: &Lrc<FileName>) -> Rc<FilePaths> {
+        if let Some(paths) = self.path_cache.borrow().get(name) {
+            return paths.clone();
+        }
+        let paths = if self.config.raw_filename {
+            // Exactly what the source map says, for globs and attribute alike.
+            let raw = name.to_string();
+            Rc::new(FilePaths {
+                emitted: raw.clone(),
+                source: raw,
+            })
+        } else {
+            let source = self.source_path(name);
+            let backslashes = match self.config.path_separator {
+                PathSeparator::Slash => false,
+                PathSeparator::Backslash => true,
+                PathSeparator::Auto => name.to_string().contains('\\'),
+            };
+            Rc::new(FilePaths {
+                emitted: self.emitted_path(source.clone(), backslashes),
+                source,
+            })
+        };
+        self.path_cache
+            .borrow_mut()
+            .insert(name.clone(), paths.clone());
+        paths
+    }
+
+    /// The file path before any `pathStyle: "basename"` / hashing / encoding.
+    ///
+    /// With `pathStyle: "absolute"` the filename is kept as SWC reports it
+    /// (forward slashes only); otherwise it is made relative to the longest
+    /// matching `cwdCandidates` entry, or else to `cwd` when one is known,
+    /// after collapsing `.` / `..` segments. `basename` is applied later, in [`Self::emitted_path`], so
+    /// file globs still see the directory.
+    fn source_path(&self, name: &FileName) -> String {
+        let raw_filename = name.to_string().replace('\\', "/");
+        let mut filename = strip_virtual_prefix(&raw_filename);
+        if self.config.clean_filenames {
+            filename = clean_filename(filename);
+        }
+        if self.config.path_style == PathStyle::Absolute {
+            return filename.to_string();
+        }
+        let filename = &normalize_path(filename);
+        let case_insensitive = self.config.case_insensitive_paths;
+        // The longest candidate containing the file, so that a package dir
+        // wins over the repo root it is nested in.
+        let candidate = self
+            .config
+            .cwd_candidates
+            .iter()
+            .filter_map(|cwd| Some((cwd.len(), strip_cwd(cwd, filename, case_insensitive)?)))
+            .max_by_key(|(len, _)| *len);
+        if let Some((_, relative)) = candidate {
+            return relative;
+        }
+        self.cwd
+            .as_ref()
+            .map(|cwd| relative_path(cwd, filename, case_insensitive))
+            .unwrap_or_else(|| filename.to_string())
+    }
+
+    /// Turns a [`Self::source_path`] into the path written to the attribute.
+    ///
+    /// Path handling works on `/` throughout; `backslashes` converts the
+    /// result for `pathSeparator` at the very end, before the `pathPrefix`
+    /// is added verbatim.
+    fn emitted_path(&self, mut path: String, backslashes: bool) -> String {
+        if self.config.path_style == PathStyle::Basename {
+            path = basename(&path).to_string();
+        }
+        if self.config.strip_extension {
+            path = strip_source_extension(&path).to_string();
+        }
+        if self.config.hash_paths {
+            path = short_hash(&path);
+        } else if self.config.leading_dot_slash && self.config.path_prefix.is_empty() {
+            path = with_leading_dot_slash(path);
+        }
+        if backslashes {
+            path = path.replace('/', "\\");
+        }
+        if self.config.url_encode_path {
+            path = percent_encode_path(&path);
+        }
+        join_path_prefix(&self.config.path_prefix, &path)
+    }
+
+    /// Whether `span` points into a pseudo file such as `<anon>`, as
+    /// macro-generated nodes can; its name is no use as a location.
+    fn is_pseudo_file(&self, span: Span) -> bool {
+        if span.is_dummy() {
+            return false;
+        }
+        let name = self.source_map.span_to_filename(span).to_string();
+        name.starts_with('<') && name.ends_with('>')
+    }
+
+    /// Applies `extensions` / `includeFiles` / `excludeFiles` /
+    /// `ignorePatterns` to the file containing `span`.
+    fn is_file_included(&self, span: Span) -> bool {
+        let include = &self.config.include_files;
+        let exclude = &self.config.exclude_files;
+        let ignore = &self.config.ignore_patterns;
+        let extensions = &self.config.extensions;
+        if span.is_dummy()
+            || (include.is_empty()
+                && exclude.is_empty()
+                && ignore.is_empty()
+                && extensions.is_empty())
+        {
+            return true;
+        }
+        let paths = self.file_paths(&self.source_map.lookup_char_pos(span.lo).file.name);
+        let path = &paths.source;
+        if !extensions.is_empty() {
+            let lowercase = path.to_ascii_lowercase();
+            if !extensions
+                .iter()
+                .any(|ext| lowercase.ends_with(ext.as_str()))
+            {
+                return false;
+            }
+        }
+        let options = MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        let matches = |patterns: &[Pattern]| patterns.iter().any(|p| p.matches_with(path, options));
+        // As in `.gitignore`, the last matching rule decides.
+        let ignored = ignore
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path, options))
+            .is_some_and(|rule| !rule.negated);
+        (include.is_empty() || matches(include)) && !matches(exclude) && !ignored
+    }
+
+    /// Applies `lineOffset` and `zeroBasedLines` to a 1-based SWC line, never
+    /// going below the first line.
+    fn display_line(&self, line: usize) -> usize {
+        let first = if self.config.zero_based_lines { 0 } else { 1 };
+        let line = line as i64 - 1 + first + i64::from(self.config.line_offset);
+        line.max(first) as usize
+    }
+
+    /// Converts SWC's 0-based `col_display` to the emitted column: 1-based
+    /// like editors, unless `zeroBasedLines` is set.
+    fn display_column(&self, col_display: usize) -> usize {
+        if self.config.zero_based_lines {
+            col_display
+        } else {
+            col_display + 1
+        }
+    }
+
+    /// Column of the last character of a span ending at `col_display`,
+    /// which is exclusive.
+    fn display_end_column(&self, col_display: usize) -> usize {
+        self.display_column(col_display).saturating_sub(1)
+    }
+
+    fn record_manifest_entry(&mut self, span: Span, element: String) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let paths = self.file_paths(&loc.file.name);
+        let hash = self.config.hash_paths.then(|| paths.emitted.clone());
+        self.manifest.push(ManifestEntry {
+            file: paths.source.clone(),
+            hash,
+            line: self.display_line(loc.line),
+            element,
+        });
+    }
+
+    /// `(attribute name, value)` pairs describing the location of `span`,
+    /// using `names` from [`source_attr_names`].
+    fn source_attrs(
+        &self,
+        span: Span,
+        element_name: &str,
+        names: &[String],
+    ) -> Vec<(String, String)> {
+        if span.is_dummy() {
+            return Vec::new();
+        }
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let line = self.display_line(loc.line);
+        let relative = self.file_paths(&loc.file.name).emitted.clone();
+        let column = self.display_column(loc.col_display);
+        let hash = self.content_hash(span);
+        let offset = (span.lo - loc.file.start_pos).0;
+        let by_offset = self.config.position_kind == PositionKind::Offset;
+
+        if self.config.split_attributes {
+            let names = names.iter().cloned();
+            let mut values = vec![relative];
+            if by_offset {
+                values.push(offset.to_string());
+            } else {
+                values.push(line.to_string());
+                if self.config.include_column {
+                    values.push(column.to_string());
+                }
+            }
+            if self.approximate {
+                values[1].push('~');
+            }
+            values.extend(hash);
+            return names.zip(values).collect();
+        }
+
+        let needs_end = self.config.include_range
+            || self
+                .config
+                .format
+                .as_ref()
+                .is_some_and(|f| f.contains("{endLine}") || f.contains("{endColumn}"));
+        let (end_line, end_column) = if needs_end {
+            let end = self.source_map.lookup_char_pos(span.hi);
+            (
+                self.display_line(end.line),
+                self.display_end_column(end.col_display),
+            )
+        } else {
+            (line, column)
+        };
+
+        let source_value = match &self.config.format {
+            Some(template) => {
+                let line = line.to_string();
+                let column = column.to_string();
+                let end_line = end_line.to_string();
+                let end_column = end_column.to_string();
+                let offset = offset.to_string();
+                render_template(template, |token| match token {
+                    "path" => Some(relative.as_str()),
+                    "line" => Some(line.as_str()),
+                    "column" => Some(column.as_str()),
+                    "endLine" => Some(end_line.as_str()),
+                    "endColumn" => Some(end_column.as_str()),
+                    "offset" => Some(offset.as_str()),
+                    "name" => Some(element_name),
+                    "hash" => hash.as_deref(),
+                    _ => None,
+                })
+            }
+            None if by_offset && self.config.include_range => {
+                let end_offset = (span.hi - loc.file.start_pos).0;
+                format!("{relative}{}{offset}-{end_offset}", self.config.separator)
+            }
+            None if by_offset => format!("{relative}{}{offset}", self.config.separator),
+            None if self.config.include_range => {
+                let (sep, col_sep) = (&self.config.separator, &self.config.column_separator);
+                format!("{relative}{sep}{line}{col_sep}{column}-{end_line}{col_sep}{end_column}")
+            }
+            None if self.config.include_column => {
+                let (sep, col_sep) = (&self.config.separator, &self.config.column_separator);
+                format!("{relative}{sep}{line}{col_sep}{column}")
+            }
+            None => format!("{relative}{}{line}", self.config.separator),
+        };
+        let mut source_value = match hash {
+            Some(hash) if self.config.format.is_none() => format!("{source_value}#{hash}"),
+            _ => source_value,
+        };
+        if self.approximate {
+            source_value.push('~');
+        }
+        vec![(names[0].clone(), source_value)]
+    }
+
+    /// `{ file, line }` object for `valueKind: "object"`, with `column` for
+    /// `includeColumn` and `endLine` / `endColumn` for `includeRange`; with
+    /// `positionKind: "offset"`, `{ file, offset }` and `endOffset`.
+    fn source_object(&self, span: Span) -> Option<Box<Expr>> {
+        if span.is_dummy() {
+            return None;
+        }
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let file = self.file_paths(&loc.file.name).emitted.clone();
+        let prop = |key: &str, value: Expr| {
+            PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(IdentName::new(key.into(), DUMMY_SP)),
+                value: Box::new(value),
+            })))
+        };
+        let num = |value: usize| {
+            Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: value as f64,
+                raw: None,
+            }))
+        };
+        let mut props = vec![prop(
+            "file",
+            Expr::Lit(Lit::Str(str_lit(file, self.config.quote_style))),
+        )];
+        if self.config.position_kind == PositionKind::Offset {
+            props.push(prop(
+                "offset",
+                num((span.lo - loc.file.start_pos).0 as usize),
+            ));
+            if self.config.include_range {
+                props.push(prop(
+                    "endOffset",
+                    num((span.hi - loc.file.start_pos).0 as usize),
+                ));
+            }
+        } else {
+            props.push(prop("line", num(self.display_line(loc.line))));
+            if self.config.include_column || self.config.include_range {
+                props.push(prop("column", num(self.display_column(loc.col_display))));
+            }
+            if self.config.include_range {
+                let end = self.source_map.lookup_char_pos(span.hi);
+                props.push(prop("endLine", num(self.display_line(end.line))));
+                props.push(prop(
+                    "endColumn",
+                    num(self.display_end_column(end.col_display)),
+                ));
+            }
+        }
+        if let Some(hash) = self.content_hash(span) {
+            let hash = str_lit(hash, self.config.quote_style);
+            props.push(prop("hash", Expr::Lit(Lit::Str(hash))));
+        }
+        if self.approximate {
+            let approximate = Expr::Lit(Lit::Bool(Bool {
+                span: DUMMY_SP,
+                value: true,
+            }));
+            props.push(prop("approximate", approximate));
+        }
+        Some(Box::new(Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props,
+        })))
+    }
+
+    /// [`short_hash`] of the element's source text, with `contentHash`.
+    fn content_hash(&self, span: Span) -> Option<String> {
+        if !self.config.content_hash {
+            return None;
+        }
+        let source = self.source_map.span_to_snippet(span).ok()?;
+        Some(short_hash(&source))
+    }
+
+    /// The element's source text for `includeSnippet`: whitespace runs
+    /// collapsed to one space and cut to `snippetLength` characters, with
+    /// `…` marking a cut.
+    fn snippet(&self, span: Span) -> Option<String> {
+        if span.is_dummy() {
+            return None;
+        }
+        let source = self.source_map.span_to_snippet(span).ok()?;
+        let mut words = source.split_whitespace();
+        let mut snippet = words.next()?.to_string();
+        for word in words {
+            snippet.push(' ');
+            snippet.push_str(word);
+        }
+        // Counted in chars so the cut never splits a multi-byte character.
+        if let Some((cut, _)) = snippet.char_indices().nth(self.config.snippet_length) {
+            snippet.truncate(cut);
+            snippet.push('…');
+        }
+        Some(snippet)
+    }
+
+    /// Adds the bindings of `import` to `imports` and notes which
+    /// `libraries` entries it uses.
+    fn record_import(&mut self, import: &ImportDecl) {
+        let source = import.src.value.to_string();
+        let libraries = &self.config.library_keys;
+        if libraries.contains(&source) {
+            self.used_libraries.insert(source.clone());
+        }
+        for specifier in &import.specifiers {
+            let (local, imported) = match specifier {
+                ImportSpecifier::Named(s) => {
+                    let imported = match &s.imported {
+                        Some(ModuleExportName::Ident(i)) => i.sym.to_string(),
+                        Some(ModuleExportName::Str(s)) => s.value.to_string(),
+                        #[cfg(swc_ast_unknown)]
+                        Some(_) => continue,
+                        None => s.local.sym.to_string(),
+                    };
+                    (&s.local, Some(imported))
+                }
+                ImportSpecifier::Default(s) => (&s.local, Some("default".to_string())),
+                ImportSpecifier::Namespace(s) => (&s.local, None),
+            };
+            // `*` covers PascalCase bindings and whatever a namespace holds.
+            let component = imported.is_none() || component_name(local).is_some();
+            if component && libraries.contains(ANY_LIBRARY) {
+                self.used_libraries.insert(ANY_LIBRARY.to_string());
+            }
+            let specific = format!("{source}#");
+            match &imported {
+                Some(export) => {
+                    let key = format!("{specific}{export}");
+                    if libraries.contains(&key) {
+                        self.used_libraries.insert(key);
+                    }
+                }
+                // Any export may be used through the namespace.
+                None => self.used_libraries.extend(
+                    libraries
+                        .iter()
+                        .filter(|key| key.starts_with(&specific))
+                        .cloned(),
+                ),
+            }
+            self.imports.insert(
+                local.sym.to_string(),
+                ImportBinding {
+                    source: source.clone(),
+                    imported,
+                },
+            );
+        }
+    }
+
+    /// Writes the attributes collected for `emitAs: "comment"` as
+    /// `/* data-component: Button, data-source: path:line */` into the
+    /// current comment slot.
+    fn push_location_comment(&mut self, pushed: Vec<(String, Option<String>)>) {
+        let Some(slot) = self.comment_slot else {
+            return;
+        };
+        let entries: Vec<_> = pushed
+            .into_iter()
+            .map(|(name, value)| match value {
+                Some(value) => format!("{name}: {value}"),
+                None => name,
+            })
+            .collect();
+        // `*/` would end the comment early.
+        let text = format!(" {} ", entries.join(", ")).replace("*/", "*\\/");
+        match slot {
+            CommentSlot::Before(pos) => {
+                if !has_comment(self.comments.get_leading(pos), &text) {
+                    self.comments.add_leading(pos, location_comment(text));
+                }
+            }
+            CommentSlot::FirstChild => self.child_comment = Some(text),
+        }
+    }
+
+    /// Puts `text` into `el` as a `{/* … */}` first child, opening up a
+    /// self-closing element. The container is given the position right after
+    /// the `<`, where nothing else has trailing comments, as the code
+    /// generator prints the comment from the trailing comments of its `{`.
+    fn push_child_comment(&self, el: &mut JSXElement, text: String) {
+        let lo = el.opening.span.lo;
+        if lo.is_dummy() {
+            return;
+        }
+        if let Some(JSXElementChild::JSXExprContainer(first)) = el.children.first() {
+            let empty = matches!(first.expr, JSXExpr::JSXEmptyExpr(_));
+            let trailing = self.comments.get_trailing(first.span.lo + BytePos(1));
+            if empty && has_comment(trailing, &text) {
+                return;
+            }
+        }
+        let span = Span::new(lo, lo + BytePos(1));
+        self.comments.add_trailing(span.hi, location_comment(text));
+        el.children.insert(
+            0,
+            JSXElementChild::JSXExprContainer(JSXExprContainer {
+                span,
+                expr: JSXExpr::JSXEmptyExpr(JSXEmptyExpr { span: DUMMY_SP }),
+            }),
+        );
+        if el.opening.self_closing {
+            el.opening.self_closing = false;
+            el.closing = Some(JSXClosingElement {
+                span: DUMMY_SP,
+                name: el.opening.name.clone(),
+            });
+        }
+    }
+
+    /// Import source of the component `element_name`; `Menu.Item` comes from
+    /// wherever `Menu` was imported.
+    fn imported_from(&self, element_name: &str) -> Option<&str> {
+        let binding = element_name.split('.').next().unwrap_or(element_name);
+        self.imports.get(binding).map(|b| b.source.as_str())
+    }
+
+    /// `libraries` attribute for the component `element_name`: a
+    /// `module#Export` entry for the export it refers to (for a namespace
+    /// import, the member used: `M.Button` is `Button`) wins over a plain
+    /// `module` entry, which wins over `*`.
+    fn library_attribute(&self, element_name: &str) -> Option<&String> {
+        let libraries = &self.config.library_attributes;
+        if libraries.is_empty() {
+            return None;
+        }
+        let mut segments = element_name.split('.');
+        let binding = self.imports.get(segments.next()?)?;
+        let export = binding.imported.as_deref().or_else(|| segments.next());
+        export
+            .and_then(|export| libraries.get(&format!("{}#{export}", binding.source)))
+            .or_else(|| libraries.get(&binding.source))
+            .or_else(|| {
+                let component = binding.imported.is_none()
+                    || element_name.starts_with(|c: char| c.is_ascii_uppercase());
+                libraries.get(ANY_LIBRARY).filter(|_| component)
+            })
+    }
+
+    /// Whether everything inside `element_name`, props included, is left
+    /// alone because of `excludeSubtree`.
+    fn excludes_subtree(&self, element_name: Option<&str>) -> bool {
+        self.config.exclude_subtree && element_name.is_some_and(|name| self.is_excluded(name))
+    }
+
+    /// Visits `node` one element deeper, for `rootOnly` and `maxDepth`.
+    fn visit_mut_nested<T: VisitMutWith<Self>>(&mut self, node: &mut T) {
+        self.depth += 1;
+        node.visit_mut_with(self);
+        self.depth -= 1;
+    }
+
+    /// Whether the children and props of `element_name` are left alone
+    /// because of `tagButSkipChildren`.
+    fn skips_children(&self, element_name: Option<&str>) -> bool {
+        element_name
+            .is_some_and(|name| self.name_set_contains(&self.config.tag_but_skip_children, name))
+    }
+
+    /// Matches `element_name` against `excluded` / `excludedPatterns`, and
+    /// for an aliased import (`import { Button as Btn }`) also the name it
+    /// was exported under, so `Btn` is excluded along with `Button`.
+    fn is_excluded(&self, element_name: &str) -> bool {
+        let excluded = |name: &str| {
+            self.config
+                .excluded_patterns
+                .iter()
+                .any(|p| p.is_match(name))
+                || self.name_set_contains(&self.config.excluded, name)
+        };
+        if excluded(element_name) {
+            return true;
+        }
+        self.exported_name(element_name)
+            .is_some_and(|exported| excluded(&exported))
+    }
+
+    /// `element_name` with its root binding replaced by the export it was
+    /// imported as, if that differs: `Btn.Item` for `import { Button as Btn }`
+    /// is `Button.Item`.
+    fn exported_name(&self, element_name: &str) -> Option<String> {
+        let (binding, rest) = match element_name.split_once('.') {
+            Some((binding, rest)) => (binding, Some(rest)),
+            None => (element_name, None),
+        };
+        let imported = self.imports.get(binding)?.imported.as_deref()?;
+        if imported == binding || imported == "default" {
+            return None;
+        }
+        Some(match rest {
+            Some(rest) => format!("{imported}.{rest}"),
+            None => imported.to_string(),
+        })
+    }
+
+    /// Looks `element_name` up in `excluded` or `included`, ignoring case
+    /// unless `caseSensitiveExclusions` is set.
+    fn name_set_contains(&self, set: &HashSet<String>, element_name: &str) -> bool {
+        if set.is_empty() {
+            false
+        } else if self.config.case_sensitive_exclusions {
+            set.contains(element_name)
+        } else if element_name.is_ascii() && element_name.len() <= LOWERCASE_BUFFER_LEN {
+            // Entries are already lowercased, so this matches `to_lowercase()`
+            // without allocating.
+            let mut buffer = [0; LOWERCASE_BUFFER_LEN];
+            let lower = &mut buffer[..element_name.len()];
+            lower.copy_from_slice(element_name.as_bytes());
+            lower.make_ascii_lowercase();
+            std::str::from_utf8(lower).is_ok_and(|lower| set.contains(lower))
+        } else {
+            set.contains(&element_name.to_lowercase())
+        }
+    }
+
+    /// Injects the configured attributes into `target` unless the element is
+    /// excluded, opted out or already tagged. `self_closing` is only known
+    /// for JSX.
+    fn tag(
+        &mut self,
+        target: &mut impl AttrTarget,
+        span: Span,
+        element_name: &str,
+        intrinsic: bool,
+        self_closing: bool,
+    ) {
+        let mut span = span;
+        if span.is_dummy() && self.config.fallback_to_parent_span {
+            if let Some(parent) = self.real_span {
+                span = parent;
+                self.approximate = true;
+            }
+        }
+        self.tag_with_span(target, span, element_name, intrinsic, self_closing);
+        self.approximate = false;
+    }
+
+    fn tag_with_span(
+        &mut self,
+        target: &mut impl AttrTarget,
+        span: Span,
+        element_name: &str,
+        intrinsic: bool,
+        self_closing: bool,
+    ) {
+        if self.config.dry_run {
+            // Go through the motions on a copy to count and record the
+            // element without changing the program.
+            let mut scratch = target.clone();
+            self.tag_in_place(&mut scratch, span, element_name, intrinsic, self_closing);
+            return;
+        }
+        if self.config.emit_as == EmitAs::Comment {
+            // The marker still has to go; nothing else touches the element.
+            if target.remove_attrs(std::slice::from_ref(&self.config.opt_out_attribute)) > 0 {
+                return;
+            }
+            let mut attrs = CommentAttrs {
+                element: target.clone(),
+                pushed: Vec::new(),
+            };
+            self.tag_in_place(&mut attrs, span, element_name, intrinsic, self_closing);
+            if !attrs.pushed.is_empty() {
+                self.push_location_comment(attrs.pushed);
+            }
+            return;
+        }
+        self.tag_in_place(target, span, element_name, intrinsic, self_closing);
+    }
+
+    fn tag_in_place(
+        &mut self,
+        target: &mut impl AttrTarget,
+        span: Span,
+        element_name: &str,
+        intrinsic: bool,
+        self_closing: bool,
+    ) {
+        // The marker is only meant for us; don't let it reach the DOM.
+        if target.remove_attrs(std::slice::from_ref(&self.config.opt_out_attribute)) > 0 {
+            return;
+        }
+        if !self.should_tag(target, span, element_name, intrinsic) {
+            return;
+        }
+        let count = target.attr_count();
+        let removed = self.inject(target, span, element_name, intrinsic, self_closing);
+        // Everything after the attributes that were kept is ours.
+        let kept = count - removed;
+        let anchor = self
+            .config
+            .insert_after
+            .as_deref()
+            .and_then(|name| target.attr_position(name))
+            .filter(|&position| position < kept);
+        let mut index = match anchor {
+            Some(position) => position + 1,
+            None if self.config.prepend_attribute => 0,
+            None => kept,
+        };
+        if let (Some(precedence), Some((first, last))) =
+            (self.config.spread_precedence, target.spread_bounds())
+        {
+            index = match precedence {
+                SpreadPrecedence::Ours => index.max(last + 1),
+                SpreadPrecedence::Theirs => index.min(first),
+            };
+        }
+        if index < kept {
+            target.move_to(kept, index);
+        }
+    }
+
+    /// Applies the element filters (`excluded`, `htmlOnly`, `rootOnly`, ...).
+    fn should_tag(
+        &self,
+        target: &impl AttrTarget,
+        span: Span,
+        element_name: &str,
+        intrinsic: bool,
+    ) -> bool {
+        if self.excluded_depth > 0 {
+            return false;
+        }
+
+        if self.is_excluded(element_name) {
+            return false;
+        }
+
+        if let Some((object, _)) = element_name.split_once('.') {
+            if self.config.ignored_member_objects.contains(object) {
+                return false;
+            }
+        }
+
+        let included = &self.config.included;
+        if !included.is_empty() && !self.name_set_contains(included, element_name) {
+            return false;
+        }
+
+        if self.config.html_only && !intrinsic {
+            return false;
+        }
+
+        // A namespaced `svg:rect` goes by its local name.
+        if self.config.valid_html_tags_only
+            && intrinsic
+            && !is_known_html_tag(element_name.rsplit(':').next().unwrap_or(element_name))
+            && !self.config.custom_elements.contains(element_name)
+        {
+            return false;
+        }
+
+        if self.config.root_only && self.depth > 0 {
+            return false;
+        }
+
+        if self.config.max_depth.is_some_and(|max| self.depth >= max) {
+            return false;
+        }
+
+        if self.config.component_boundary_only && !self.boundary_roots.contains(&span) {
+            return false;
+        }
+
+        if self.config.only_interactive
+            && !(intrinsic && INTERACTIVE_TAGS.contains(&element_name))
+            && !has_click_handler(target)
+        {
+            return false;
+        }
+
+        if self.config.require_click_handler && !has_click_handler(target) {
+            return false;
+        }
+
+        // `Fragment` only accepts `key` and `children`; anything else warns.
+        if !self.config.tag_fragments && is_fragment(element_name) {
+            return false;
+        }
+
+        // A spread may already carry a value for the attribute.
+        if self.config.skip_on_spread && target.has_spread() {
+            return false;
+        }
+
+        if self.is_pseudo_file(span) {
+            return false;
+        }
+
+        self.is_file_included(span)
+    }
+
+    /// Pushes the attributes onto `target`, returning how many existing ones
+    /// were removed by `overwriteExisting`.
+    fn inject(
+        &mut self,
+        target: &mut impl AttrTarget,
+        span: Span,
+        element_name: &str,
+        intrinsic: bool,
+        self_closing: bool,
+    ) -> usize {
+        enum SourceValue {
+            Object(Box<Expr>),
+            Attrs(Vec<(String, String)>),
+        }
+
+        let quote = self.config.quote_style;
+        // Taken before anything of ours is added.
+        let prop_count = target.prop_count();
+        let (module, library) = if intrinsic {
+            (None, None)
+        } else {
+            (
+                self.imported_from(element_name).map(str::to_string),
+                self.library_attribute(element_name),
+            )
+        };
+        let library_names;
+        let names = match library {
+            Some(base) => {
+                library_names = source_attr_names(base, self.config);
+                &library_names
+            }
+            None => &self.source_attr_names,
+        };
+        let existing = names.iter().any(|name| target.has_attr(name));
+        if existing && !self.config.overwrite_existing {
+            return 0;
+        }
+        // Worked out before the other attributes so that an element left
+        // untagged is also left untouched.
+        let source = if self.config.value_kind == ValueKind::Object {
+            match self.source_object(span) {
+                Some(object) => SourceValue::Object(object),
+                None => return 0,
+            }
+        } else {
+            let attrs = self.source_attrs(span, element_name, names);
+            if attrs.is_empty() {
+                return 0;
+            }
+            SourceValue::Attrs(attrs)
+        };
+
+        let component_attr = &self.config.component_attribute_name;
+        if self.config.include_component_name && !target.has_attr(component_attr) {
+            target.push_attr(component_attr, element_name.to_string(), quote);
+        }
+
+        if self.config.component_boundary_only {
+            let boundary_attr = format!("{}-component", self.config.attribute_name);
+            if let Some(component) = &self.component {
+                if !target.has_attr(&boundary_attr) {
+                    target.push_attr(&boundary_attr, component.clone(), quote);
+                }
+            }
+        }
+
+        if self.config.include_element_id {
+            let id_attr = format!("{}-id", self.config.attribute_name);
+            if !target.has_attr(&id_attr) {
+                target.push_attr(&id_attr, self.next_element_id.to_string(), quote);
+                self.next_element_id += 1;
+            }
+        }
+
+        if self.config.include_self_closing && self_closing {
+            let self_closing_attr = format!("{}-selfclosing", self.config.attribute_name);
+            if !target.has_attr(&self_closing_attr) {
+                target.push_flag_attr(&self_closing_attr, quote);
+            }
+        }
+
+        if self.config.include_prop_count {
+            let props_attr = format!("{}-props", self.config.attribute_name);
+            if !target.has_attr(&props_attr) {
+                target.push_attr(&props_attr, prop_count.to_string(), quote);
+            }
+        }
+
+        if self.config.include_module {
+            let module_attr = format!("{}-module", self.config.attribute_name);
+            if let Some(source) = module {
+                if !target.has_attr(&module_attr) {
+                    target.push_attr(&module_attr, source, quote);
+                }
+            }
+        }
+
+        if self.config.include_snippet {
+            let snippet_attr = format!("{}-snippet", self.config.attribute_name);
+            if !target.has_attr(&snippet_attr) {
+                if let Some(snippet) = self.snippet(span) {
+                    target.push_attr(&snippet_attr, snippet, quote);
+                }
+            }
+        }
+
+        let removed = if existing {
+            target.remove_attrs(names)
+        } else {
+            0
+        };
+        match source {
+            SourceValue::Object(object) => target.push_expr_attr(&names[0], object, quote),
+            SourceValue::Attrs(attrs) => {
+                for (name, value) in attrs {
+                    target.push_attr(&name, value, quote);
+                }
+            }
+        }
+
+        self.tagged_count += 1;
+        if self.config.emit_manifest {
+            self.record_manifest_entry(span, element_name.to_string());
+        }
+        removed
+    }
+}
+
+impl<S: ?Sized + SourceMapper> VisitMut for ReactSourceStringVisitor<'_, S> {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        self.imports.clear();
+        self.next_element_id = 0;
+        for item in &module.body {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                self.record_import(import);
+            }
+        }
+        module.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_fn_decl(&mut self, decl: &mut FnDecl) {
+        if self.config.component_boundary_only {
+            self.next_component = component_name(&decl.ident);
+        }
+        decl.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_var_declarator(&mut self, decl: &mut VarDeclarator) {
+        decl.name.visit_mut_with(self);
+        if self.config.component_boundary_only {
+            if let (Pat::Ident(binding), Some(init)) = (&decl.name, &decl.init) {
+                if is_component_function(init) {
+                    self.next_component = component_name(&binding.id);
+                }
+            }
+        }
+        decl.init.visit_mut_with(self);
+    }
+
+    /// `export default function Page() {}` and other named function
+    /// expressions not already named by an assignment.
+    fn visit_mut_fn_expr(&mut self, expr: &mut FnExpr) {
+        if self.config.component_boundary_only && self.next_component.is_none() {
+            self.next_component = expr.ident.as_ref().and_then(component_name);
+        }
+        expr.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_class_decl(&mut self, decl: &mut ClassDecl) {
+        let name = component_name(&decl.ident);
+        let parent = std::mem::replace(&mut self.class_component, name);
+        decl.visit_mut_children_with(self);
+        self.class_component = parent;
+    }
+
+    fn visit_mut_class_expr(&mut self, expr: &mut ClassExpr) {
+        let name = expr.ident.as_ref().and_then(component_name);
+        let parent = std::mem::replace(&mut self.class_component, name);
+        expr.visit_mut_children_with(self);
+        self.class_component = parent;
+    }
+
+    /// The `render()` of a class component is its boundary.
+    fn visit_mut_class_method(&mut self, method: &mut ClassMethod) {
+        let render =
+            !method.is_static && matches!(&method.key, PropName::Ident(key) if key.sym == "render");
+        if self.config.component_boundary_only && render {
+            self.next_component = self.class_component.clone();
+        }
+        method.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_function(&mut self, function: &mut Function) {
+        let component = self.next_component.take();
+        let parent = std::mem::replace(&mut self.component, component);
+        function.visit_mut_children_with(self);
+        self.component = parent;
+    }
+
+    fn visit_mut_arrow_expr(&mut self, arrow: &mut ArrowExpr) {
+        let component = self.next_component.take();
+        let parent = std::mem::replace(&mut self.component, component);
+        let parent_roots = match &*arrow.body {
+            BlockStmtOrExpr::Expr(body) if self.component.is_some() => Some(std::mem::replace(
+                &mut self.boundary_roots,
+                returned_roots(body),
+            )),
+            _ => None,
+        };
+        arrow.visit_mut_children_with(self);
+        if let Some(parent_roots) = parent_roots {
+            self.boundary_roots = parent_roots;
+        }
+        self.component = parent;
+    }
+
+    fn visit_mut_return_stmt(&mut self, ret: &mut ReturnStmt) {
+        let parent_roots = match &ret.arg {
+            Some(arg) if self.component.is_some() => Some(std::mem::replace(
+                &mut self.boundary_roots,
+                returned_roots(arg),
+            )),
+            _ => None,
+        };
+        ret.visit_mut_children_with(self);
+        if let Some(parent_roots) = parent_roots {
+            self.boundary_roots = parent_roots;
+        }
+    }
+
+    fn visit_mut_jsx_element(&mut self, el: &mut JSXElement) {
+        let parent = self.element_span.replace(el.span);
+        let parent_real = self.real_span;
+        if !el.span.is_dummy() {
+            self.real_span = Some(el.span);
+        }
+        let name = Self::jsx_element_name_str(&el.opening.name);
+        let excludes_subtree = self.excludes_subtree(name.as_deref());
+        let skips_children = self.skips_children(name.as_deref());
+        // Fragments group siblings without adding a level of nesting.
+        let nests = !name.is_some_and(|n| is_fragment(&n));
+        if excludes_subtree {
+            self.excluded_depth += 1;
+        }
+        el.opening.visit_mut_with(self);
+        if let Some(text) = self.child_comment.take() {
+            self.push_child_comment(el, text);
+        }
+        if nests {
+            self.depth += 1;
+        }
+        if skips_children {
+            self.excluded_depth += 1;
+        }
+        el.children.visit_mut_with(self);
+        el.closing.visit_mut_with(self);
+        if skips_children {
+            self.excluded_depth -= 1;
+        }
+        if nests {
+            self.depth -= 1;
+        }
+        if excludes_subtree {
+            self.excluded_depth -= 1;
+        }
+        self.element_span = parent;
+        self.real_span = parent_real;
+    }
+
+    fn visit_mut_jsx_opening_element(&mut self, el: &mut JSXOpeningElement) {
+        let skips_children = self.skips_children(Self::jsx_element_name_str(&el.name).as_deref());
+        // Elements passed as attribute values are not nested in this one:
+        // only children add a level.
+        if skips_children {
+            self.excluded_depth += 1;
+        }
+        el.visit_mut_children_with(self);
+        if skips_children {
+            self.excluded_depth -= 1;
+        }
+
+        let element_name = match Self::jsx_element_name_str(&el.name) {
+            Some(n) => n,
+            None => return,
+        };
+        let span = self.element_span.unwrap_or(el.span);
+        let intrinsic = self.is_intrinsic(&el.name);
+        // The code generator prints no comments in front of a JSX element, and
+        // `</* … */div>` would read as a closing tag, so the comment goes in
+        // front of the first attribute, or into the children without one.
+        self.comment_slot = Some(
+            el.attrs
+                .iter()
+                .find_map(|attr| match attr {
+                    JSXAttrOrSpread::JSXAttr(attr) => Some(attr.span.lo),
+                    JSXAttrOrSpread::SpreadElement(_) => None,
+                })
+                .filter(|pos| !pos.is_dummy())
+                .map_or(CommentSlot::FirstChild, CommentSlot::Before),
+        );
+        self.tag(
+            &mut el.attrs,
+            span,
+            &element_name,
+            intrinsic,
+            el.self_closing,
+        );
+        self.comment_slot = None;
+    }
+
+    /// With `styledComponents`, rewrites `styled.div\`…\`` to
+    /// `styled.div.attrs({ "data-source": "…" })\`…\``, so the rendered
+    /// element carries the location of the styled component's definition.
+    fn visit_mut_tagged_tpl(&mut self, tpl: &mut TaggedTpl) {
+        tpl.visit_mut_children_with(self);
+        if !self.config.styled_components {
+            return;
+        }
+        let Some((element_name, intrinsic)) = styled_target(&tpl.tag) else {
+            return;
+        };
+        let mut props = ObjectLit {
+            span: DUMMY_SP,
+            props: Vec::new(),
+        };
+        // In front of the tag: `/* data-source: … */ styled.div\`…\``.
+        self.comment_slot = Some(tpl.span.lo)
+            .filter(|pos| !pos.is_dummy())
+            .map(CommentSlot::Before);
+        self.tag(&mut props, tpl.span, &element_name, intrinsic, false);
+        self.comment_slot = None;
+        if props.props.is_empty() {
+            return;
+        }
+        let styled = std::mem::replace(&mut *tpl.tag, Expr::Invalid(Invalid { span: DUMMY_SP }));
+        *tpl.tag = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(styled),
+                prop: MemberProp::Ident(IdentName::new("attrs".into(), DUMMY_SP)),
+            }))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Object(props)),
+            }],
+            ..Default::default()
+        });
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        let factory = element_factory(&call.callee, self.pragma.as_deref(), &self.imports);
+        let element_name = call
+            .args
+            .first()
+            .and_then(|arg| element_type_name(&arg.expr));
+        let nests = factory.is_some() && !element_name.as_deref().is_some_and(is_fragment);
+        // A `tagButSkipChildren` element is only tagged below, once its
+        // arguments have been visited.
+        let excludes_subtree = factory.is_some()
+            && (self.excludes_subtree(element_name.as_deref())
+                || self.skips_children(element_name.as_deref()));
+        if excludes_subtree {
+            self.excluded_depth += 1;
+        }
+        let parent_real = self.real_span;
+        if factory.is_some() && !call.span.is_dummy() {
+            self.real_span = Some(call.span);
+        }
+        call.callee.visit_mut_with(self);
+        call.type_args.visit_mut_with(self);
+        // Like attributes, elements passed as props are not nested: only
+        // `createElement` children and the runtime's `children` prop are.
+        for (index, arg) in call.args.iter_mut().enumerate() {
+            match factory.filter(|_| nests) {
+                Some(ElementFactory::CreateElement) if index >= 2 => self.visit_mut_nested(arg),
+                Some(ElementFactory::JsxRuntime) if index == 1 => match &mut *arg.expr {
+                    Expr::Object(props) => {
+                        for prop in &mut props.props {
+                            if is_children_prop(prop) {
+                                self.visit_mut_nested(prop);
+                            } else {
+                                prop.visit_mut_with(self);
+                            }
+                        }
+                    }
+                    _ => arg.visit_mut_with(self),
+                },
+                _ => arg.visit_mut_with(self),
+            }
+        }
+        self.real_span = parent_real;
+        if excludes_subtree {
+            self.excluded_depth -= 1;
+        }
+
+        let (Some(factory), Some(element_name)) = (factory, element_name) else {
+            return;
+        };
+        // Compiled host elements are passed by tag name: `jsx("div", ...)`.
+        let intrinsic = matches!(&*call.args[0].expr, Expr::Lit(Lit::Str(_)));
+        let span = call.span;
+        // In front of the callee: `/* data-source: … */ _jsx("div", ...)`.
+        self.comment_slot = Some(span.lo)
+            .filter(|pos| !pos.is_dummy())
+            .map(CommentSlot::Before);
+        match call.args.get_mut(1) {
+            Some(ExprOrSpread { spread: None, expr }) => match &mut **expr {
+                Expr::Object(props) => self.tag(props, span, &element_name, intrinsic, false),
+                // `createElement("div", null)`: replace `null` with a props object
+                Expr::Lit(Lit::Null(_)) if factory == ElementFactory::CreateElement => {
+                    let mut props = ObjectLit {
+                        span: DUMMY_SP,
+                        props: Vec::new(),
+                    };
+                    self.tag(&mut props, span, &element_name, intrinsic, false);
+                    if !props.props.is_empty() {
+                        **expr = Expr::Object(props);
+                    }
+                }
+                _ => {}
+            },
+            // `createElement("div")` without props
+            None if factory == ElementFactory::CreateElement => {
+                let mut props = ObjectLit {
+                    span: DUMMY_SP,
+                    props: Vec::new(),
+                };
+                self.tag(&mut props, span, &element_name, intrinsic, false);
+                if !props.props.is_empty() {
+                    call.args.push(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(Expr::Object(props)),
+                    });
+                }
+            }
+            _ => {}
+        }
+        self.comment_slot = None;
+    }
+}
+
+/// Injects the source attributes into `program`.
+///
+/// `source_map` resolves spans to file positions; the manifest, if enabled,
+/// is attached through `comments`. `config.root` is the directory paths are
+/// made relative to.
+///
+/// Calls share nothing but the read-only `config`, so files can be
+/// transformed one after another or on several threads at once.
+///
+/// `warnUnusedLibraries` warnings go to SWC's `HANDLER`, and are dropped
+/// when it isn't set.
+pub fn transform<S: ?Sized + SourceMapper>(
+    mut program: Program,
+    source_map: &S,
+    comments: impl Comments,
+    config: &ParsedConfig,
+) -> Program {
+    if config.mode == Mode::Production
+        || has_disable_directive(&program, &comments, &config.disable_directive)
+    {
+        return program;
+    }
+    let mut visitor = ReactSourceStringVisitor::new(config, source_map, &comments);
+    visitor.pragma = jsx_pragma(&program, &comments);
+    program.visit_mut_with(&mut visitor);
+    emit_manifest(&program, &comments, &visitor.manifest);
+    if config.dry_run {
+        emit_dry_run(&program, &comments, visitor.tagged_count);
+    }
+    if config.warn_unused_libraries {
+        let mut unused: Vec<_> = config
+            .library_keys
+            .iter()
+            .filter(|module| !visitor.used_libraries.contains(*module))
+            .collect();
+        unused.sort();
+        for module in unused {
+            config_warning(&format!(
+                "`libraries` entry {module:?} is not imported in this file"
+            ));
+        }
+    }
+    program
+}
+
+#[plugin_transform]
+pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+    let config_str = metadata.get_transform_plugin_config();
+    let Some(mut config) = parse_config(config_str.as_deref()) else {
+        return program;
+    };
+    if config.root.as_deref().is_none_or(str::is_empty) {
+        config.root = metadata.get_experimental_context("cwd");
+    }
+    let env = metadata.get_context(&TransformPluginMetadataContextKind::Env);
+    config.mode = config.mode.resolve(env.as_deref());
+    transform(program, &metadata.source_map, metadata.comments, &config)
 }
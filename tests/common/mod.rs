@@ -0,0 +1,22 @@
+use swc_core::common::util::take::Take;
+use swc_core::ecma::ast::{fn_pass, Pass};
+use swc_core::ecma::parser::{Syntax, TsSyntax};
+use swc_core::ecma::transforms::testing::Tester;
+use swc_plugin_react_source_string::{parse_config, transform};
+
+pub fn syntax() -> Syntax {
+    Syntax::Typescript(TsSyntax {
+        tsx: true,
+        ..Default::default()
+    })
+}
+
+/// The plugin as a pass, configured with the options JSON `config`.
+pub fn react_source(tester: &Tester, config: Option<&str>) -> impl Pass {
+    let cm = tester.cm.clone();
+    let comments = tester.comments.clone();
+    let config = parse_config(config).expect("valid config");
+    fn_pass(move |program| {
+        program.map_with_mut(|program| transform(program, &*cm, &comments, &config))
+    })
+}
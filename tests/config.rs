@@ -0,0 +1,94 @@
+use swc_plugin_react_source_string::{parse_config, validate_config};
+
+fn problems(config: &str) -> Vec<String> {
+    validate_config(Some(config)).err().unwrap_or_default()
+}
+
+#[test]
+fn valid_configs_have_no_problems() {
+    assert!(validate_config(None).is_ok());
+    assert!(validate_config(Some("{}")).is_ok());
+    assert!(validate_config(Some(r#"{ "includeColumn": true, "rootOnly": true }"#)).is_ok());
+    assert!(validate_config(Some(r#"{ "libraries": ["@mui/material"] }"#)).is_ok());
+    assert!(validate_config(Some(r#"{ "libraries": ["*"] }"#)).is_ok());
+}
+
+#[test]
+fn every_unknown_key_and_bad_value_is_reported() {
+    let problems = problems(r#"{ "atributeName": "x", "includeColumn": "yes", "rotOnly": true }"#);
+    assert_eq!(problems.len(), 3, "{problems:?}");
+    assert!(problems[0].contains("`atributeName`"), "{problems:?}");
+    assert!(problems[1].contains("invalid type"), "{problems:?}");
+    assert!(problems[2].contains("`rotOnly`"), "{problems:?}");
+}
+
+#[test]
+fn bad_patterns_are_reported_together() {
+    let problems = problems(
+        r#"{ "includeFiles": ["src/[", "ok/**"], "excludedPatterns": ["("], "ignorePatterns": ["a/**[/"] }"#,
+    );
+    assert_eq!(problems.len(), 3, "{problems:?}");
+    assert!(problems[0].contains("`includeFiles`"), "{problems:?}");
+    assert!(problems[1].contains("`ignorePatterns`"), "{problems:?}");
+    assert!(problems[2].contains("`excludedPatterns`"), "{problems:?}");
+}
+
+#[test]
+fn conflicting_options_are_reported() {
+    let cases = [
+        (
+            r#"{ "splitAttributes": true, "format": "{path}" }"#,
+            "`format` is ignored with `splitAttributes`",
+        ),
+        (
+            r#"{ "valueKind": "object", "format": "{path}" }"#,
+            "`format` is ignored with `valueKind: \"object\"`",
+        ),
+        (
+            r#"{ "valueKind": "object", "splitAttributes": true }"#,
+            "`splitAttributes` is ignored with `valueKind: \"object\"`",
+        ),
+        (
+            r#"{ "emitAs": "comment", "valueKind": "object" }"#,
+            "`valueKind: \"object\"` is ignored with `emitAs: \"comment\"`",
+        ),
+        (
+            r#"{ "absolutePaths": true, "pathStyle": "basename" }"#,
+            "`absolutePaths` is ignored with `pathStyle`",
+        ),
+        (
+            r#"{ "rootOnly": true, "maxDepth": 3 }"#,
+            "`maxDepth` has no effect with `rootOnly`",
+        ),
+        (
+            r#"{ "htmlOnly": true, "includeModule": true }"#,
+            "`includeModule` has no effect with `htmlOnly`",
+        ),
+        (
+            r#"{ "htmlOnly": true, "libraries": { "lib": { "attribute": "data-lib" } } }"#,
+            "`libraries` has no effect with `htmlOnly`",
+        ),
+        (
+            r#"{ "rawFilename": true, "hashPaths": true }"#,
+            "`hashPaths` is ignored with `rawFilename`",
+        ),
+    ];
+    for (config, expected) in cases {
+        assert_eq!(problems(config), [expected], "{config}");
+    }
+}
+
+#[test]
+fn problems_are_not_cut_short() {
+    let problems = problems(
+        r#"{ "separator": "", "attributeName": "1bad", "rawFilename": true, "stripExtension": true, "urlEncodePath": true }"#,
+    );
+    assert_eq!(problems.len(), 4, "{problems:?}");
+}
+
+/// Without an SWC handler, problems are dropped instead of panicking.
+#[test]
+fn parse_config_works_outside_a_handler_scope() {
+    assert!(parse_config(Some(r#"{ "separator": "" }"#)).is_some());
+    assert!(parse_config(Some(r#"{ "rootOnly": "yes" }"#)).is_none());
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use swc_core::ecma::transforms::testing::{test_fixture, FixtureTestConfig};
+use swc_core::testing::fixture;
+
+/// Runs `input.tsx` through the plugin with the options in `config.json`, if
+/// any, and compares against `output.tsx`. `UPDATE=1 cargo test` rewrites
+/// the expected output.
+#[fixture("tests/fixture/**/input.tsx")]
+fn fixture(input: PathBuf) {
+    let dir = input.parent().unwrap();
+    let config = fs::read_to_string(dir.join("config.json")).ok();
+    test_fixture(
+        common::syntax(),
+        &|tester| common::react_source(tester, config.as_deref()),
+        &input,
+        &dir.join("output.tsx"),
+        FixtureTestConfig::default(),
+    );
+}
@@ -0,0 +1 @@
+export const App = ()=><Button data-acme-component="Button" data-acme-source-file="input.js" data-acme-source-line="1"/>;
@@ -0,0 +1,3 @@
+export const App = ()=><div data-source="input.js:2:3-4:8">
+    <span data-source="input.js:3:5-3:21">text</span>
+  </div>;
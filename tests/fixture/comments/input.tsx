@@ -0,0 +1,18 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+// Leading comment on the statement.
+export const App = () => (
+  <div
+    // on an attribute
+    id="app"
+  >
+    {/* inside the children */}
+    <Button />
+  </div>
+);
+
+/** Compiled output keeps its comments too. */
+export const Compiled = () =>
+  _jsx("div", {
+    // on a prop
+    id: "compiled",
+  });
@@ -0,0 +1,12 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+// Leading comment on the statement.
+export const App = ()=><div data-source="input.js:4" // on an attribute
+    id="app">
+    { /* inside the children */ }
+    <Button data-source="input.js:9"/>
+  </div>;
+/** Compiled output keeps its comments too. */ export const Compiled = ()=>_jsx("div", {
+        "data-source": "input.js:15",
+        // on a prop
+        id: "compiled"
+    });
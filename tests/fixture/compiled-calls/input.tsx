@@ -0,0 +1,7 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+import React from "react";
+
+export const a = _jsx("div", { children: _jsx(Button, {}) });
+export const b = React.createElement("span", null);
+export const c = React.createElement(Card);
+export const d = other("div", {});
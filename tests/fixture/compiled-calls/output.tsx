@@ -0,0 +1,15 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+import React from "react";
+export const a = _jsx("div", {
+    children: _jsx(Button, {
+        "data-source": "input.js:4"
+    }),
+    "data-source": "input.js:4"
+});
+export const b = React.createElement("span", {
+    "data-source": "input.js:5"
+});
+export const c = React.createElement(Card, {
+    "data-source": "input.js:6"
+});
+export const d = other("div", {});
@@ -0,0 +1,42 @@
+export function Card({ items }) {
+  const icon = <Icon />;
+  return (
+    <section>
+      {icon}
+      {items.map((item) => <Item key={item} />)}
+    </section>
+  );
+}
+
+export const Header = () => <header><h1>Title</h1></header>;
+
+function helper() {
+  return <div />;
+}
+
+export default function Page() {
+  return <main><Card items={[]} /></main>;
+}
+
+export const Memoized = memo(() => <article><p /></article>);
+
+export const Input = React.forwardRef((props, ref) => <input ref={ref} {...props} />);
+
+export function Status({ ok, error }) {
+  if (error) {
+    return error && <Alert>{error}</Alert>;
+  }
+  return ok ? <Check /> : <Cross><span /></Cross>;
+}
+
+export const Either = (props) => (props.a || <Fallback />);
+
+export class Profile extends React.Component {
+  render() {
+    return <aside><img /></aside>;
+  }
+
+  renderItem() {
+    return <li />;
+  }
+}
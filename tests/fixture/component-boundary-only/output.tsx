@@ -0,0 +1,31 @@
+export function Card({ items }) {
+    const icon = <Icon/>;
+    return <section data-source-component="Card" data-source="input.js:4">
+      {icon}
+      {items.map((item)=><Item key={item}/>)}
+    </section>;
+}
+export const Header = ()=><header data-source-component="Header" data-source="input.js:11"><h1>Title</h1></header>;
+function helper() {
+    return <div/>;
+}
+export default function Page() {
+    return <main data-source-component="Page" data-source="input.js:18"><Card items={[]}/></main>;
+}
+export const Memoized = memo(()=><article data-source-component="Memoized" data-source="input.js:21"><p/></article>);
+export const Input = React.forwardRef((props, ref)=><input ref={ref} {...props} data-source-component="Input" data-source="input.js:23"/>);
+export function Status({ ok, error }) {
+    if (error) {
+        return error && <Alert data-source-component="Status" data-source="input.js:27">{error}</Alert>;
+    }
+    return ok ? <Check data-source-component="Status" data-source="input.js:29"/> : <Cross data-source-component="Status" data-source="input.js:29"><span/></Cross>;
+}
+export const Either = (props)=>props.a || <Fallback data-source-component="Either" data-source="input.js:32"/>;
+export class Profile extends React.Component {
+    render() {
+        return <aside data-source-component="Profile" data-source="input.js:36"><img/></aside>;
+    }
+    renderItem() {
+        return <li/>;
+    }
+}
@@ -0,0 +1,11 @@
+import { Button } from "@mui/material";
+
+function Card({ title }: { title: string }) {
+  return <section>{title}</section>;
+}
+
+export const App = () => (
+  <Card title="card">
+    <Button variant="contained">Save</Button>
+  </Card>
+);
@@ -0,0 +1,9 @@
+import { Button } from "@mui/material";
+function Card({ title }: {
+    title: string;
+}) {
+    return <section data-source="input.js:4">{title}</section>;
+}
+export const App = ()=><Card title="card" data-source="input.js:8">
+    <Button variant="contained" data-source="input.js:9">Save</Button>
+  </Card>;
@@ -0,0 +1,14 @@
+export const View = ({ cond, flag, items, rows }) => (
+  <main>
+    {cond ? <A /> : <B />}
+    {flag && <C />}
+    {flag || <D />}
+    {items.map((item) => <Item key={item} />)}
+    {rows.map((row) => row.map((cell) => <Cell key={cell} />))}
+    {(() => {
+      return <E />;
+    })()}
+  </main>
+);
+
+export const Conditional = ({ cond }) => (cond ? <F /> : null);
@@ -0,0 +1,11 @@
+export const View = ({ cond, flag, items, rows })=><main data-source="input.js:2">
+    {cond ? <A data-source="input.js:3"/> : <B data-source="input.js:3"/>}
+    {flag && <C data-source="input.js:4"/>}
+    {flag || <D data-source="input.js:5"/>}
+    {items.map((item)=><Item key={item} data-source="input.js:6"/>)}
+    {rows.map((row)=>row.map((cell)=><Cell key={cell} data-source="input.js:7"/>))}
+    {(()=>{
+        return <E data-source="input.js:9"/>;
+    })()}
+  </main>;
+export const Conditional = ({ cond })=>cond ? <F data-source="input.js:14"/> : null;
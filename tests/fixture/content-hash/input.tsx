@@ -0,0 +1,6 @@
+export const List = () => (
+  <ul>
+    <li>same</li>
+    <li>same</li>
+  </ul>
+);
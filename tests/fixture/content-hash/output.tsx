@@ -0,0 +1,4 @@
+export const List = ()=><ul data-source="input.js:2#8b956ace">
+    <li data-source="input.js:3#fc8c6dd2">same</li>
+    <li data-source="input.js:4#fc8c6dd2">same</li>
+  </ul>;
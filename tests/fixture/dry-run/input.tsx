@@ -0,0 +1,6 @@
+export const App = () => (
+  <div>
+    <Button data-no-source />
+    <span data-source="kept" />
+  </div>
+);
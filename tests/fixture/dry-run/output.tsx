@@ -0,0 +1,4 @@
+/* @react-source-manifest [{"file":"input.js","line":2,"element":"div"}] */ /* @react-source-dry-run {"count":1} */ export const App = ()=><div>
+    <Button data-no-source/>
+    <span data-source="kept"/>
+  </div>;
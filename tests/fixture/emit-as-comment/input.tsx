@@ -0,0 +1,14 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const App = (props) => (
+  <main>
+    <Button variant="primary" />
+    {items.map((item) => <Item key={item} {...item} />)}
+    <hr />
+    <div {...props} />
+    <section {...props}>text</section>
+  </main>
+);
+
+export const Compiled = () => _jsx("div", { id: "compiled" });
+
+export const Title = styled.h1`color: red;`;
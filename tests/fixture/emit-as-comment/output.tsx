@@ -0,0 +1,12 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const App = (props)=><main>{ /* data-component: main, data-source: input.js:3 */ }
+    <Button /* data-component: Button, data-source: input.js:4 */ variant="primary"/>
+    {items.map((item)=><Item /* data-component: Item, data-source: input.js:5 */ key={item} {...item}/>)}
+    <hr>{ /* data-component: hr, data-source: input.js:6 */ }</hr>
+    <div {...props}>{ /* data-component: div, data-source: input.js:7 */ }</div>
+    <section {...props}>{ /* data-component: section, data-source: input.js:8 */ }text</section>
+  </main>;
+export const Compiled = ()=>/* data-component: div, data-source: input.js:12 */ _jsx("div", {
+        id: "compiled"
+    });
+export const Title = /* data-component: h1, data-source: input.js:14 */ styled.h1`color: red;`;
@@ -0,0 +1,3 @@
+/* @react-source-manifest [{"file":"input.js","line":2,"element":"div"},{"file":"input.js","line":3,"element":"Button"}] */ export const App = ()=><div data-source="input.js:2">
+    <Button label="*/ in a value" data-source="input.js:3"/>
+  </div>;
@@ -0,0 +1,10 @@
+export const Doc = () => (
+  <article>
+    <CodeBlock icon={<Icon />}>
+      <pre>
+        <code>x</code>
+      </pre>
+    </CodeBlock>
+    <p>after</p>
+  </article>
+);
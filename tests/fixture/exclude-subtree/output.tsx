@@ -0,0 +1,8 @@
+export const Doc = ()=><article data-source="input.js:2">
+    <CodeBlock icon={<Icon/>}>
+      <pre>
+        <code>x</code>
+      </pre>
+    </CodeBlock>
+    <p data-source="input.js:8">after</p>
+  </article>;
@@ -0,0 +1,11 @@
+export const App = () => (
+  <div>
+    <Trans>hello</Trans>
+    <trans>lowercase</trans>
+    <IconHome />
+    <IconUser />
+    <StyledButton />
+    <MyStyled />
+    <Link />
+  </div>
+);
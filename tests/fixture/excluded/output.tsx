@@ -0,0 +1,9 @@
+export const App = ()=><div data-source="input.js:2">
+    <Trans>hello</Trans>
+    <trans>lowercase</trans>
+    <IconHome/>
+    <IconUser/>
+    <StyledButton/>
+    <MyStyled data-source="input.js:8"/>
+    <Link data-source="input.js:9"/>
+  </div>;
@@ -0,0 +1,5 @@
+export const App = () => (
+  <div data-source="keep.tsx:1">
+    <span {...props} />
+  </div>
+);
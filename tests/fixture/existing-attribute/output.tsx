@@ -0,0 +1,3 @@
+export const App = ()=><div data-source="keep.tsx:1">
+    <span {...props} data-source="input.js:3"/>
+  </div>;
@@ -0,0 +1 @@
+export const App = ()=><Button data-loc="Button@input.js#L1">{"{}"}</Button>;
@@ -0,0 +1,12 @@
+import React, { Fragment } from "react";
+
+export const List = () => (
+  <>
+    <Fragment key="a">
+      <li>a</li>
+    </Fragment>
+    <React.Fragment>
+      <li>b</li>
+    </React.Fragment>
+  </>
+);
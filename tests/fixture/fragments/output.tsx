@@ -0,0 +1,9 @@
+import React, { Fragment } from "react";
+export const List = ()=><>
+    <Fragment key="a">
+      <li data-source="input.js:6">a</li>
+    </Fragment>
+    <React.Fragment>
+      <li data-source="input.js:9">b</li>
+    </React.Fragment>
+  </>;
@@ -0,0 +1,12 @@
+export function Page() {
+  return (
+    <main>
+      <h1 className="title">Hello</h1>
+      <img src="/logo.png" />
+      <svg:rect width={10} />
+      <xlink:href />
+      <svg:circle r={5} />
+      <my-widget />
+    </main>
+  );
+}
@@ -0,0 +1,10 @@
+export function Page() {
+    return <main data-component="main" data-source="input.js:3">
+      <h1 className="title" data-component="h1" data-source="input.js:4">Hello</h1>
+      <img src="/logo.png" data-component="img" data-source="input.js:5"/>
+      <svg:rect width={10} data-component="svg:rect" data-source="input.js:6"/>
+      <xlink:href data-component="xlink:href" data-source="input.js:7"/>
+      <svg:circle r={5}/>
+      <my-widget data-component="my-widget" data-source="input.js:9"/>
+    </main>;
+}
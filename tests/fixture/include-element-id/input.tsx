@@ -0,0 +1,7 @@
+export const App = () => (
+  <ul>
+    <li>a</li>
+    <li>b</li>
+  </ul>
+);
+export const Other = () => <Item />;
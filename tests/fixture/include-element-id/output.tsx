@@ -0,0 +1,5 @@
+export const App = ()=><ul data-source-id="0" data-source="input.js:2">
+    <li data-source-id="1" data-source="input.js:3">a</li>
+    <li data-source-id="2" data-source="input.js:4">b</li>
+  </ul>;
+export const Other = ()=><Item data-source-id="3" data-source="input.js:7"/>;
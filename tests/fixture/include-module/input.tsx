@@ -0,0 +1,12 @@
+import { Button } from "@mui/material";
+import * as Antd from "antd";
+import Local from "./Local";
+
+export const Form = () => (
+  <form>
+    <Button />
+    <Antd.Input />
+    <Local />
+    <Unbound />
+  </form>
+);
@@ -0,0 +1,9 @@
+import { Button } from "@mui/material";
+import * as Antd from "antd";
+import Local from "./Local";
+export const Form = ()=><form data-source="input.js:6">
+    <Button data-source-module="@mui/material" data-mui-source="input.js:7"/>
+    <Antd.Input data-source-module="antd" data-source="input.js:8"/>
+    <Local data-source-module="./Local" data-source="input.js:9"/>
+    <Unbound data-source="input.js:10"/>
+  </form>;
@@ -0,0 +1,6 @@
+export const App = () => (
+  <div>
+    <img src="a.png" />
+    <Icon />
+  </div>
+);
@@ -0,0 +1,4 @@
+export const App = ()=><div data-source="input.js:2">
+    <img src="a.png" data-source-selfclosing data-source="input.js:3"/>
+    <Icon data-source-selfclosing data-source="input.js:4"/>
+  </div>;
@@ -0,0 +1,8 @@
+export const Form = () => (
+  <form>
+    <Button variant="primary" onClick={() => save("draft")}>
+      Sauvegarder l’état
+    </Button>
+    <p title="it's">Café</p>
+  </form>
+);
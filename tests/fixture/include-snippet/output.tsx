@@ -0,0 +1,6 @@
+export const Form = ()=><form data-source-snippet='<form> <Button variant="primar…' data-source="input.js:2">
+    <Button variant="primary" onClick={()=>save("draft")} data-source-snippet='<Button variant="primary" onCl…' data-source="input.js:3">
+      Sauvegarder l’état
+    </Button>
+    <p title="it's" data-source-snippet="<p title=&quot;it's&quot;>Café</p>" data-source="input.js:6">Café</p>
+  </form>;
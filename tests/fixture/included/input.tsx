@@ -0,0 +1,8 @@
+export const App = () => (
+  <div>
+    <Button />
+    <button />
+    <Link />
+    <Menu.Item />
+  </div>
+);
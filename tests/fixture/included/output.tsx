@@ -0,0 +1,6 @@
+export const App = ()=><div>
+    <Button data-source="input.js:3"/>
+    <button data-source="input.js:4"/>
+    <Link/>
+    <Menu.Item data-source="input.js:6"/>
+  </div>;
@@ -0,0 +1,6 @@
+/** @jsx h */
+import { h } from "preact";
+
+export const a = h("div", null, h(Button, { primary: true }));
+export const b = h("span");
+export const c = other("div", {});
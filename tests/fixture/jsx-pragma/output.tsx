@@ -0,0 +1,11 @@
+/** @jsx h */ import { h } from "preact";
+export const a = h("div", {
+    "data-source": "input.js:4"
+}, h(Button, {
+    primary: true,
+    "data-source": "input.js:4"
+}));
+export const b = h("span", {
+    "data-source": "input.js:5"
+});
+export const c = other("div", {});
@@ -0,0 +1,11 @@
+export const App = () => (
+  <Layout>
+    <>
+      <section>
+        <div>
+          <span />
+        </div>
+      </section>
+    </>
+  </Layout>
+);
@@ -0,0 +1,9 @@
+export const App = ()=><Layout data-source="input.js:2">
+    <>
+      <section data-source="input.js:4">
+        <div>
+          <span/>
+        </div>
+      </section>
+    </>
+  </Layout>;
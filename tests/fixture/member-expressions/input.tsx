@@ -0,0 +1,8 @@
+export const Nav = () => (
+  <Menu>
+    <Menu.Item>
+      <Menu.Item.Icon />
+    </Menu.Item>
+    <Icon />
+  </Menu>
+);
@@ -0,0 +1,6 @@
+export const Nav = ()=><Menu data-component="Menu" data-source="input.js:2">
+    <Menu.Item data-component="Menu.Item" data-source="input.js:3">
+      <Menu.Item.Icon/>
+    </Menu.Item>
+    <Icon data-component="Icon" data-source="input.js:6"/>
+  </Menu>;
@@ -0,0 +1,13 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+
+export const App = () => (
+  <div>
+    <button>ok</button>
+    <a href="/">home</a>
+    <span onClick={toggle} />
+    <Card onClick={open} />
+    <Card {...handlers} />
+    <p>text</p>
+  </div>
+);
+export const compiled = _jsx("li", { onClick: select });
@@ -0,0 +1,13 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const App = ()=><div>
+    <button data-source="input.js:5">ok</button>
+    <a href="/" data-source="input.js:6">home</a>
+    <span onClick={toggle} data-source="input.js:7"/>
+    <Card onClick={open} data-source="input.js:8"/>
+    <Card {...handlers}/>
+    <p>text</p>
+  </div>;
+export const compiled = _jsx("li", {
+    onClick: select,
+    "data-source": "input.js:13"
+});
@@ -0,0 +1,9 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+
+export const App = () => (
+  <div>
+    <Chart data-no-source />
+    <Chart data-skip />
+  </div>
+);
+export const compiled = _jsx("span", { "data-skip": true, id: "x" });
@@ -0,0 +1,8 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const App = ()=><div data-source="input.js:4">
+    <Chart data-no-source data-source="input.js:5"/>
+    <Chart/>
+  </div>;
+export const compiled = _jsx("span", {
+    id: "x"
+});
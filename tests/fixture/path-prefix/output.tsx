@@ -0,0 +1 @@
+export const App = ()=><div data-source="webpack://app/input.js:1"/>;
@@ -0,0 +1,5 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+
+export const a = <Button {...props} data-source="kept" />;
+export const b = <div id="x" {...props} />;
+export const c = _jsx("span", { ...props, id: "y" });
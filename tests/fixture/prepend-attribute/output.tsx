@@ -0,0 +1,9 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const a = <Button data-component="Button" data-source="input.js:3" {...props}/>;
+export const b = <div data-component="div" data-source="input.js:4" id="x" {...props}/>;
+export const c = _jsx("span", {
+    "data-component": "span",
+    "data-source": "input.js:5",
+    ...props,
+    id: "y"
+});
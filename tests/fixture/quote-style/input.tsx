@@ -0,0 +1,4 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+
+export const a = <Button />;
+export const b = _jsx("div", {});
@@ -0,0 +1,6 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const a = <Button data-component='Button' data-source='input.js:3'/>;
+export const b = _jsx("div", {
+    'data-component': 'div',
+    'data-source': 'input.js:4'
+});
@@ -0,0 +1,7 @@
+export const App = () => (
+  <div>
+    <button>ok</button>
+    <span onClickCapture={toggle} />
+    <Card onClick={open} />
+  </div>
+);
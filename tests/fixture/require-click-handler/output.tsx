@@ -0,0 +1,5 @@
+export const App = ()=><div>
+    <button>ok</button>
+    <span onClickCapture={toggle} data-source="input.js:4"/>
+    <Card onClick={open} data-source="input.js:5"/>
+  </div>;
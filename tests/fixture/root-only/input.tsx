@@ -0,0 +1,7 @@
+export const App = () => (
+  <Layout>
+    <div>
+      <span />
+    </div>
+  </Layout>
+);
@@ -0,0 +1,5 @@
+export const App = ()=><Layout data-source="input.js:2">
+    <div>
+      <span/>
+    </div>
+  </Layout>;
@@ -0,0 +1,3 @@
+export const App = ()=><div data-source="input.js#2,3">
+    <span data-source="input.js#3,5"/>
+  </div>;
@@ -0,0 +1 @@
+export const App = ()=><div data-source-file="input.js" data-source-line="1" data-source-column="26">  <span data-source-file="input.js" data-source-line="1" data-source-column="33"/></div>;
@@ -0,0 +1,15 @@
+import styled from "styled-components";
+
+const Title = styled.h1`
+  font-size: 2em;
+`;
+
+const Primary = styled(Button)`
+  color: red;
+`;
+
+const Themed = styled.div.attrs({ role: "note" })`
+  padding: 1em;
+`;
+
+export const Page = () => <Title>Hello</Title>;
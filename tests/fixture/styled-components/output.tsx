@@ -0,0 +1,17 @@
+import styled from "styled-components";
+const Title = styled.h1.attrs({
+    "data-source": "input.js:3"
+})`
+  font-size: 2em;
+`;
+const Primary = styled(Button).attrs({
+    "data-source": "input.js:7"
+})`
+  color: red;
+`;
+const Themed = styled.div.attrs({
+    role: "note"
+})`
+  padding: 1em;
+`;
+export const Page = ()=><Title data-source="input.js:15">Hello</Title>;
@@ -0,0 +1,12 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const Welcome = ({ name }) => (
+  <section>
+    <Trans i18nKey="welcome" components={{ bold: <strong /> }}>
+      Hello <b>{name}</b>, <Link to="/inbox">open your inbox</Link>.
+    </Trans>
+    <p>Untouched siblings are still tagged.</p>
+  </section>
+);
+
+export const Compiled = () =>
+  _jsx(Trans, { i18nKey: "compiled", children: _jsx("b", { children: "bold" }) });
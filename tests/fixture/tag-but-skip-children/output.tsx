@@ -0,0 +1,16 @@
+import { jsx as _jsx } from "react/jsx-runtime";
+export const Welcome = ({ name })=><section data-source="input.js:3">
+    <Trans i18nKey="welcome" components={{
+        bold: <strong/>
+    }} data-source="input.js:4">
+      Hello <b>{name}</b>, <Link to="/inbox">open your inbox</Link>.
+    </Trans>
+    <p data-source="input.js:7">Untouched siblings are still tagged.</p>
+  </section>;
+export const Compiled = ()=>_jsx(Trans, {
+        i18nKey: "compiled",
+        children: _jsx("b", {
+            children: "bold"
+        }),
+        "data-source": "input.js:12"
+    });
@@ -0,0 +1,11 @@
+export const App = ()=><div data-source={{
+        file: "input.js",
+        line: 2,
+        column: 3
+    }}>
+    <Button data-source={{
+        file: "input.js",
+        line: 3,
+        column: 5
+    }}/>
+  </div>;
@@ -0,0 +1,5 @@
+export const App = () => (
+  <div>
+    <span>text</span>
+  </div>
+);
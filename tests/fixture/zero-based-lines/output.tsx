@@ -0,0 +1,3 @@
+export const App = ()=><div data-source="input.js:1:2-3:7">
+    <span data-source="input.js:2:4-2:20">text</span>
+  </div>;
@@ -0,0 +1,923 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use swc_core::common::comments::SingleThreadedComments;
+use swc_core::common::errors::{DiagnosticBuilder, Emitter, Handler, HANDLER};
+use swc_core::common::util::take::Take;
+use swc_core::common::{SourceMap, DUMMY_SP};
+use swc_core::ecma::ast::{fn_pass, JSXElement, JSXElementName, Module, Program};
+use swc_core::ecma::transforms::testing::{test_inline, Tester};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use swc_plugin_react_source_string::{parse_config, transform, ParsedConfig};
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, None),
+    tags_html_and_components,
+    r#"const a = <div><Button /></div>;"#,
+    r#"const a = <div data-source="input.js:1"><Button data-source="input.js:1" /></div>;"#
+);
+
+/// Import bindings from one module must not leak into the next one.
+#[test]
+fn imports_are_scoped_to_their_module() {
+    Tester::run(|tester| {
+        let mut pass = common::react_source(tester, Some(r#"{ "includeModule": true }"#));
+        let syntax = common::syntax();
+        let imported = tester.apply_transform(
+            &mut pass,
+            "imported.tsx",
+            syntax,
+            Some(true),
+            r#"import { Button } from "lib"; <Button />;"#,
+        )?;
+        let local = tester.apply_transform(
+            &mut pass,
+            "local.tsx",
+            syntax,
+            Some(true),
+            r#"const Button = () => null; <Button />;"#,
+        )?;
+        let comments = tester.comments.clone();
+        assert!(tester
+            .print(&imported, &comments)
+            .contains(r#"data-source-module="lib""#));
+        assert!(!tester
+            .print(&local, &comments)
+            .contains("data-source-module"));
+        Ok(())
+    });
+}
+
+/// Transforms `src` as the file `filename` and returns the printed output.
+fn transform_file(config: &str, filename: &str, src: &str) -> String {
+    Tester::run(|tester| {
+        let pass = common::react_source(tester, Some(config));
+        let program = tester.apply_transform(pass, filename, common::syntax(), Some(true), src)?;
+        let comments = tester.comments.clone();
+        Ok(tester.print(&program, &comments))
+    })
+}
+
+/// Imports, manifest entries and counts are per file, and the parsed config
+/// can be shared between threads.
+#[test]
+fn per_file_state_is_not_shared() {
+    fn assert_shareable<T: Send + Sync>() {}
+    assert_shareable::<ParsedConfig>();
+
+    let config = r#"{ "emitManifest": true, "dryRun": true, "warnUnusedLibraries": true, "libraries": { "lib": { "attribute": "data-lib" } } }"#;
+    Tester::run(|tester| {
+        let mut pass = common::react_source(tester, Some(config));
+        let syntax = common::syntax();
+        let first = tester.apply_transform(
+            &mut pass,
+            "first.tsx",
+            syntax,
+            Some(true),
+            r#"import { Button } from "lib"; <><Button /><div /></>;"#,
+        )?;
+        let second =
+            tester.apply_transform(&mut pass, "second.tsx", syntax, Some(true), "<p />;")?;
+        let comments = tester.comments.clone();
+        let first = tester.print(&first, &comments);
+        let second = tester.print(&second, &comments);
+        assert!(first.contains(r#"{"count":2}"#), "{first}");
+        assert!(second.contains(r#"{"count":1}"#), "{second}");
+        assert!(!second.contains("first.tsx"), "{second}");
+        Ok(())
+    });
+}
+
+/// Collects the messages of emitted diagnostics.
+#[derive(Clone, Default)]
+struct Diagnostics(Arc<Mutex<Vec<String>>>);
+
+impl Emitter for Diagnostics {
+    fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push(db.message());
+    }
+}
+
+/// Like [`transform_file`], but returns the diagnostics instead.
+fn transform_diagnostics(config: &str, src: &str) -> Vec<String> {
+    let diagnostics = Diagnostics::default();
+    let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+    Tester::run(|tester| {
+        HANDLER.set(&handler, || {
+            let pass = common::react_source(tester, Some(config));
+            tester.apply_transform(pass, "input.tsx", common::syntax(), Some(true), src)
+        })?;
+        Ok(())
+    });
+    let messages = diagnostics.0.lock().unwrap().clone();
+    messages
+}
+
+/// Warnings are dropped, not a panic, outside SWC's handler scope.
+#[test]
+fn transform_works_outside_a_handler_scope() {
+    let config = parse_config(Some(
+        r#"{ "warnUnusedLibraries": true, "libraries": ["lib"] }"#,
+    ))
+    .expect("valid config");
+    let program = Program::Module(Module::dummy());
+    let source_map = SourceMap::default();
+    transform(
+        program,
+        &source_map,
+        SingleThreadedComments::default(),
+        &config,
+    );
+}
+
+/// The array form lists entries without overrides.
+#[test]
+fn libraries_array_form() {
+    let config = r#"{ "warnUnusedLibraries": true, "libraries": ["@mui/material", "lib"] }"#;
+    let src = r#"import { Button } from "@mui/material"; <Button />;"#;
+    let messages = transform_diagnostics(config, src);
+    assert_eq!(
+        messages,
+        [r#"swc-plugin-react-source-string: `libraries` entry "lib" is not imported in this file"#],
+    );
+    let out = transform_file(config, "input.tsx", src);
+    assert!(
+        out.contains(r#"<Button data-source="input.tsx:1"/>"#),
+        "{out}"
+    );
+}
+
+/// `["*"]` covers every imported component, and `excluded` still applies.
+#[test]
+fn wildcard_library_list() {
+    let config = r#"{ "warnUnusedLibraries": true, "libraries": ["*"], "excluded": ["Dialog"] }"#;
+    let src = r#"import { Dialog, Tooltip } from "@mui/material"; <><Dialog /><Tooltip /></>;"#;
+    assert!(transform_diagnostics(config, src).is_empty());
+    let out = transform_file(config, "input.tsx", src);
+    assert!(
+        out.contains(r#"<Dialog/><Tooltip data-source="input.tsx:1"/>"#),
+        "{out}"
+    );
+    let messages = transform_diagnostics(config, r#"import "./styles.css"; <div />;"#);
+    assert_eq!(
+        messages,
+        [r#"swc-plugin-react-source-string: `libraries` entry "*" is not imported in this file"#],
+    );
+}
+
+/// Entries without an `attribute` are tracked too.
+#[test]
+fn warns_about_unused_libraries() {
+    let config = r#"{ "warnUnusedLibraries": true, "libraries": { "lib": {}, "ui": { "attribute": "data-ui" } } }"#;
+    let messages = transform_diagnostics(config, r#"import { Button } from "ui"; <Button />;"#);
+    assert_eq!(
+        messages,
+        [r#"swc-plugin-react-source-string: `libraries` entry "lib" is not imported in this file"#],
+    );
+    let messages = transform_diagnostics(
+        config,
+        r#"import { Button } from "ui"; import { Card } from "lib"; <Button />;"#,
+    );
+    assert!(messages.is_empty(), "{messages:?}");
+    let config = r#"{ "warnUnusedLibraries": true, "libraries": { "lib": {} } }"#;
+    let messages = transform_diagnostics(config, r#"import { Card } from "lib"; <Card />;"#);
+    assert!(messages.is_empty(), "{messages:?}");
+}
+
+/// An invalid pattern is reported and skipped; the others still apply.
+#[test]
+fn invalid_excluded_pattern_is_skipped() {
+    let config = r#"{ "excludedPatterns": ["(Icon", "^Styled"] }"#;
+    let src = "<><IconHome /><StyledButton /></>;";
+    let messages = transform_diagnostics(config, src);
+    assert_eq!(messages.len(), 1, "{messages:?}");
+    assert!(messages[0].contains("`excludedPatterns`"), "{messages:?}");
+    let out = transform_file(config, "input.tsx", src);
+    assert!(
+        out.contains(r#"<IconHome data-source="input.tsx:1"/><StyledButton/>"#),
+        "{out}"
+    );
+}
+
+/// Every element of a file gets the path resolved for its first one, and
+/// the next file through the same pass resolves its own.
+#[test]
+fn file_paths_are_resolved_per_file() {
+    let config = r#"{ "root": "/repo", "urlEncodePath": true }"#;
+    Tester::run(|tester| {
+        let mut pass = common::react_source(tester, Some(config));
+        let syntax = common::syntax();
+        let src = "<div>\n  <span />\n  <Button />\n</div>;";
+        let first =
+            tester.apply_transform(&mut pass, "/repo/src/my app.tsx", syntax, Some(true), src)?;
+        let second =
+            tester.apply_transform(&mut pass, "/repo/lib/other.tsx", syntax, Some(true), src)?;
+        let comments = tester.comments.clone();
+        let first = tester.print(&first, &comments);
+        let second = tester.print(&second, &comments);
+        for (out, path) in [(&first, "src/my%20app.tsx"), (&second, "lib/other.tsx")] {
+            for line in 1..=3 {
+                assert!(
+                    out.contains(&format!(r#"data-source="{path}:{line}""#)),
+                    "{out}"
+                );
+            }
+            assert_eq!(out.matches("data-source=").count(), 3, "{out}");
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn longest_cwd_candidate_wins() {
+    let config =
+        r#"{ "cwdCandidates": ["/repo", "/repo/packages/app", "/repo/packages/application"] }"#;
+    let out = transform_file(config, "/repo/packages/app/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+    let out = transform_file(config, "/repo/tools/x.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="tools/x.tsx:1""#), "{out}");
+    let out = transform_file(config, "/elsewhere/x.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="/elsewhere/x.tsx:1""#), "{out}");
+}
+
+#[test]
+fn hash_paths_replace_the_path() {
+    let out = transform_file(
+        r#"{ "hashPaths": true, "root": "/repo" }"#,
+        "/repo/src/App.tsx",
+        "<div />;",
+    );
+    assert!(out.contains(r#"data-source="26ad4b83:1""#), "{out}");
+}
+
+#[test]
+fn strip_extension_drops_only_the_last_one() {
+    let config = r#"{ "stripExtension": true, "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/App.test.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.test:1""#), "{out}");
+}
+
+#[test]
+fn url_encode_path_escapes_reserved_characters() {
+    let config = r#"{ "urlEncodePath": true, "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/my app/#1?é.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="src/my%20app/%231%3F%C3%A9.tsx:1""#),
+        "{out}"
+    );
+}
+
+#[test]
+fn path_styles() {
+    let config = r#"{ "pathStyle": "basename", "root": "/repo", "includeFiles": ["src/**"] }"#;
+    let out = transform_file(config, "/repo/src/pages/Home.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="Home.tsx:1""#), "{out}");
+    let config = r#"{ "absolutePaths": true, "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/App.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="/repo/src/App.tsx:1""#),
+        "{out}"
+    );
+    // `pathStyle` wins over the older shorthand.
+    let config = r#"{ "absolutePaths": true, "pathStyle": "relative", "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+}
+
+#[test]
+fn path_separators() {
+    let config = r#"{ "pathSeparator": "\\", "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src\App.tsx:1""#), "{out}");
+    let config = r#"{ "pathSeparator": "auto", "root": "C:/repo" }"#;
+    let out = transform_file(config, "C:\\repo\\src\\App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src\App.tsx:1""#), "{out}");
+    let config = r#"{ "pathSeparator": "auto", "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+}
+
+#[test]
+fn case_insensitive_paths() {
+    let config = r#"{ "caseInsensitivePaths": true, "root": "C:\\repo" }"#;
+    let out = transform_file(config, "c:/repo/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+    // A longer directory name is not inside the root.
+    let out = transform_file(config, "C:\\repository\\src\\App.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="C:/repository/src/App.tsx:1""#),
+        "{out}"
+    );
+}
+
+#[test]
+fn include_and_exclude_files() {
+    let config =
+        r#"{ "includeFiles": ["src/**"], "excludeFiles": ["**/*.stories.tsx"], "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+    let out = transform_file(config, "/repo/src/App.stories.tsx", "<div />;");
+    assert!(!out.contains("data-source"), "{out}");
+    let out = transform_file(config, "/repo/lib/App.tsx", "<div />;");
+    assert!(!out.contains("data-source"), "{out}");
+}
+
+#[test]
+fn line_offset_shifts_lines_but_not_below_one() {
+    let out = transform_file(r#"{ "lineOffset": 10 }"#, "App.tsx", "\n<div />;");
+    assert!(out.contains(r#"data-source="App.tsx:12""#), "{out}");
+    let out = transform_file(r#"{ "lineOffset": -5 }"#, "App.tsx", "\n<div />;");
+    assert!(out.contains(r#"data-source="App.tsx:1""#), "{out}");
+}
+
+/// The root only matches whole directories, with or without a trailing `/`.
+#[test]
+fn root_matches_at_path_boundaries() {
+    let out = transform_file(r#"{ "root": "src" }"#, "src-utils/App.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="src-utils/App.tsx:1""#),
+        "{out}"
+    );
+    let out = transform_file(r#"{ "root": "src" }"#, "src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="App.tsx:1""#), "{out}");
+    let out = transform_file(r#"{ "root": "/repo/" }"#, "/repo-old/App.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="/repo-old/App.tsx:1""#),
+        "{out}"
+    );
+    let out = transform_file(r#"{ "root": "/repo/" }"#, "/repo/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="App.tsx:1""#), "{out}");
+}
+
+#[test]
+fn redundant_path_segments_are_collapsed() {
+    let config = r#"{ "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/./src//components/../App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+    let out = transform_file(config, "/other/../repo/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+    let out = transform_file("{}", "./src/../../lib/./x.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="../lib/x.tsx:1""#), "{out}");
+    let out = transform_file("{}", "src\\components//App.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="src/components/App.tsx:1""#),
+        "{out}"
+    );
+    let out = transform_file(config, "/repo\\src\\.\\App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+    let config = r#"{ "root": "/repo/./packages//app/" }"#;
+    let out = transform_file(config, "/repo/packages/app/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+}
+
+#[test]
+fn clean_filenames_strips_loaders_and_queries() {
+    let config = r#"{ "root": "/repo", "cleanFilenames": true }"#;
+    let out = transform_file(
+        config,
+        "style-loader!css-loader?modules!/repo/src/App.tsx",
+        "<div />;",
+    );
+    assert!(out.contains(r#"data-source="src/App.tsx:1""#), "{out}");
+    let out = transform_file(
+        config,
+        "/repo/src/App.vue?vue&type=script&lang.tsx",
+        "<div />;",
+    );
+    assert!(out.contains(r#"data-source="src/App.vue:1""#), "{out}");
+    let out = transform_file(
+        r#"{ "root": "/repo" }"#,
+        "/repo/src/App.vue?vue",
+        "<div />;",
+    );
+    assert!(out.contains(r#"data-source="src/App.vue?vue:1""#), "{out}");
+}
+
+#[test]
+fn ignore_patterns_follow_gitignore_rules() {
+    let config = r##"{
+        "root": "/repo",
+        "ignorePatterns": ["# generated", "generated/", "*.stories.tsx", "!keep.stories.tsx", "/legacy"]
+    }"##;
+    let tagged =
+        |filename: &str| transform_file(config, filename, "<div />;").contains("data-source");
+    assert!(!tagged("/repo/src/generated/api.tsx"));
+    assert!(!tagged("/repo/src/Button.stories.tsx"));
+    assert!(tagged("/repo/src/keep.stories.tsx"));
+    assert!(!tagged("/repo/legacy/Old.tsx"));
+    assert!(tagged("/repo/src/legacy/New.tsx"));
+    assert!(tagged("/repo/src/generated.tsx"));
+}
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "includeComponentName": true, "overwriteExisting": true }"#)
+    ),
+    fragments_keep_their_key,
+    r#"import { Fragment } from "react"; const a = <><React.Fragment key={x}><li /></React.Fragment><Fragment key={y} /></>;"#,
+    r#"import { Fragment } from "react"; const a = <><React.Fragment key={x}><li data-component="li" data-source="input.js:1" /></React.Fragment><Fragment key={y} /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "libraries": { "@mui/material": { "attribute": "data-mui-source" } } }"#)
+    ),
+    namespace_imports_match_by_binding,
+    r#"import * as M from "@mui/material"; const a = <><M.Button /><Other.Button /></>;"#,
+    r#"import * as M from "@mui/material"; const a = <><M.Button data-mui-source="input.js:1" /><Other.Button data-source="input.js:1" /></>;"#
+);
+
+#[test]
+fn raw_filename_is_emitted_untouched() {
+    let config = r#"{ "rawFilename": true, "root": "/repo", "pathPrefix": "web" }"#;
+    let out = transform_file(config, "/repo/src/../App.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="/repo/src/../App.tsx:1""#),
+        "{out}"
+    );
+}
+
+#[test]
+fn extensions_limit_tagged_files() {
+    let config = r#"{ "extensions": [".TSX", "mdx"] }"#;
+    let out = transform_file(config, "src/App.tsx", "<div />;");
+    assert!(out.contains("data-source"), "{out}");
+    let out = transform_file(config, "src/Docs.mdx", "<div />;");
+    assert!(out.contains("data-source"), "{out}");
+    let out = transform_file(config, "vendor/widget.jsx", "<div />;");
+    assert!(!out.contains("data-source"), "{out}");
+}
+
+#[test]
+fn pseudo_files_are_not_tagged() {
+    let out = transform_file("{}", "<anon>", "<div />;");
+    assert!(!out.contains("data-source"), "{out}");
+    let out = transform_file("{}", "<jsx-runtime>", "<div />;");
+    assert!(!out.contains("data-source"), "{out}");
+}
+
+#[test]
+fn disable_directive_skips_the_file() {
+    let out = transform_file("{}", "App.tsx", "/* @no-react-source */\n<div />;");
+    assert!(!out.contains("data-source"), "{out}");
+    let out = transform_file("{}", "App.tsx", "/* not @no-react-source-ish */\n<div />;");
+    assert!(out.contains("data-source"), "{out}");
+    let config = r#"{ "disableDirective": "@untagged" }"#;
+    let out = transform_file(config, "App.tsx", "/**\n * @untagged\n */\n<div />;");
+    assert!(!out.contains("data-source"), "{out}");
+    // Next.js files start with a directive.
+    let src = "\"use client\";\n/* @no-react-source */\n<div />;";
+    let out = transform_file("{}", "App.tsx", src);
+    assert!(!out.contains("data-source"), "{out}");
+    let src = "\"use client\";\n<div />;\n/* @no-react-source */\n<p />;";
+    let out = transform_file("{}", "App.tsx", src);
+    assert!(out.contains("data-source"), "{out}");
+}
+
+#[test]
+fn leading_dot_slash_marks_relative_paths() {
+    let config = r#"{ "leadingDotSlash": true, "root": "/repo" }"#;
+    let out = transform_file(config, "/repo/src/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="./src/App.tsx:1""#), "{out}");
+    let out = transform_file(config, "/other/App.tsx", "<div />;");
+    assert!(out.contains(r#"data-source="/other/App.tsx:1""#), "{out}");
+    let config = r#"{ "leadingDotSlash": true, "root": "/repo", "pathPrefix": "webpack://app" }"#;
+    let out = transform_file(config, "/repo/src/App.tsx", "<div />;");
+    assert!(
+        out.contains(r#"data-source="webpack://app/src/App.tsx:1""#),
+        "{out}"
+    );
+}
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "htmlOnly": true, "componentDetection": "firstUpper" }"#)
+    ),
+    first_upper_component_detection,
+    r#"const a = <div><myWidget /><Button /></div>;"#,
+    r#"const a = <div data-source="input.js:1"><myWidget data-source="input.js:1" /><Button /></div>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(
+            r#"{ "htmlOnly": true, "componentDetection": "notHtmlTag", "customElements": ["my-widget"] }"#
+        )
+    ),
+    not_html_tag_component_detection,
+    r#"const a = <div><my-widget /><x-unknown /><Menu /></div>;"#,
+    r#"const a = <div data-source="input.js:1"><my-widget data-source="input.js:1" /><x-unknown /><Menu /></div>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(
+            r#"{ "framework": "vue", "htmlOnly": true, "customElements": ["my-widget"], "attributePrefix": "v" }"#
+        )
+    ),
+    vue_kebab_case_components,
+    r#"const a = <div><router-link /><my-widget /><span /><Menu /></div>;"#,
+    r#"const a = <div data-v-source="input.js:1"><router-link /><my-widget data-v-source="input.js:1" /><span data-v-source="input.js:1" /><Menu /></div>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "prependAttribute": true, "spreadPrecedence": "ours" }"#)
+    ),
+    spread_precedence_ours,
+    r#"const a = <><div {...props} id="a" /><div id="b" {...props} /></>;"#,
+    r#"const a = <><div {...props} data-source="input.js:1" id="a" /><div id="b" {...props} data-source="input.js:1" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "spreadPrecedence": "theirs" }"#)),
+    spread_precedence_theirs,
+    r#"const a = <><div {...props} id="a" /><div id="b" {...props} /></>;"#,
+    r#"const a = <><div data-source="input.js:1" {...props} id="a" /><div id="b" data-source="input.js:1" {...props} /></>;"#
+);
+
+#[test]
+fn special_characters_in_paths_are_escaped() {
+    let config = r#"{ "rawFilename": true, "quoteStyle": "single" }"#;
+    let filename = r#"src/we"ird\App.tsx"#;
+    let out = transform_file(config, filename, "<div />;");
+    assert!(
+        out.contains(r#"data-source="src/we&quot;ird\App.tsx:1""#),
+        "{out}"
+    );
+    let out = transform_file(
+        config,
+        filename,
+        r#"import { jsx } from "react/jsx-runtime"; jsx("div", {});"#,
+    );
+    assert!(
+        out.contains(r#"'data-source': 'src/we"ird\\App.tsx:1'"#),
+        "{out}"
+    );
+}
+
+/// Drops the spans of `<synthetic>` elements, as a macro might.
+struct DummySynthetic;
+
+impl VisitMut for DummySynthetic {
+    fn visit_mut_jsx_element(&mut self, el: &mut JSXElement) {
+        el.visit_mut_children_with(self);
+        let name = &el.opening.name;
+        if matches!(name, JSXElementName::Ident(i) if i.sym == "synthetic") {
+            el.span = DUMMY_SP;
+            el.opening.span = DUMMY_SP;
+        }
+    }
+}
+
+test_inline!(
+    common::syntax(),
+    |t| (
+        fn_pass(|program| program.visit_mut_with(&mut DummySynthetic)),
+        common::react_source(t, Some(r#"{ "fallbackToParentSpan": true }"#))
+    ),
+    dummy_spans_fall_back_to_parent,
+    r#"const a = <div>
+    <synthetic />
+</div>;"#,
+    r#"const a = <div data-source="input.js:1">
+    <synthetic data-source="input.js:1~" />
+</div>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| (
+        fn_pass(|program| program.visit_mut_with(&mut DummySynthetic)),
+        common::react_source(t, None)
+    ),
+    dummy_spans_are_skipped_by_default,
+    r#"const a = <div><synthetic /></div>;"#,
+    r#"const a = <div data-source="input.js:1"><synthetic /></div>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| (
+        fn_pass(|program| program.visit_mut_with(&mut DummySynthetic)),
+        common::react_source(
+            t,
+            Some(
+                r#"{ "includeComponentName": true, "includeElementId": true, "includePropCount": true, "includeModule": true, "includeSnippet": true }"#
+            )
+        )
+    ),
+    untagged_elements_are_left_untouched,
+    r#"import { Button } from "lib";
+const a = <><Button data-source="hand" /><synthetic /><div /></>;"#,
+    r#"import { Button } from "lib";
+const a = <><Button data-source="hand" /><synthetic /><div data-component="div" data-source-id="0" data-source-props="0" data-source-snippet="<div />" data-source="input.js:2" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "insertAfter": "className" }"#)),
+    insert_after_anchor_attribute,
+    r#"const a = <><div className="box" id="a" /><div id="b" /></>;"#,
+    r#"const a = <><div className="box" data-source="input.js:1" id="a" /><div id="b" data-source="input.js:1" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "htmlOnly": true, "componentsFromImportsOnly": true }"#)
+    ),
+    lowercase_imported_components,
+    r#"import { counter } from "./counter"; const a = <main><counter /><Widget /></main>;"#,
+    r#"import { counter } from "./counter"; const a = <main data-source="input.js:1"><counter /><Widget data-source="input.js:1" /></main>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "includeModule": true, "componentsFromImportsOnly": true }"#)
+    ),
+    lowercase_imported_components_report_their_module,
+    r#"import { counter } from "./counter"; const a = <counter />;"#,
+    r#"import { counter } from "./counter"; const a = <counter data-source-module="./counter" data-source="input.js:1" />;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "positionKind": "offset" }"#)),
+    offsets_count_utf8_bytes,
+    r#"const s = "é"; <div />;"#,
+    r#"const s = "é"; <div data-source="input.js:16" />;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(
+            r#"{ "libraries": { "@acme/ui#Button": { "attribute": "data-ui-button" }, "@acme/ui": { "attribute": "data-ui" } } }"#
+        )
+    ),
+    libraries_match_specific_exports,
+    r#"import { Button as Btn, Card } from "@acme/ui"; import { Button } from "@acme/legacy"; import * as UI from "@acme/ui";
+const a = <><Btn /><Card /><Button /><UI.Button /></>;"#,
+    r#"import { Button as Btn, Card } from "@acme/ui"; import { Button } from "@acme/legacy"; import * as UI from "@acme/ui";
+const a = <><Btn data-ui-button="input.js:2" /><Card data-ui="input.js:2" /><Button data-source="input.js:2" /><UI.Button data-ui-button="input.js:2" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(
+            r#"{ "libraries": { "*": { "attribute": "data-lib" }, "@acme/ui": { "attribute": "data-ui" } }, "excluded": ["Dialog"] }"#
+        )
+    ),
+    wildcard_library_matches_any_import,
+    r#"import { Card } from "@acme/ui"; import { Dialog, Tooltip } from "@mui/material"; import * as M from "@mui/icons"; import Local from "./Local";
+const a = <><Card /><Dialog /><Tooltip /><M.Add /><Local /><Unimported /><div /></>;"#,
+    r#"import { Card } from "@acme/ui"; import { Dialog, Tooltip } from "@mui/material"; import * as M from "@mui/icons"; import Local from "./Local";
+const a = <><Card data-ui="input.js:2" /><Dialog /><Tooltip data-lib="input.js:2" /><M.Add data-lib="input.js:2" /><Local data-lib="input.js:2" /><Unimported data-source="input.js:2" /><div data-source="input.js:2" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "excluded": ["Button", "Menu.Item"] }"#)),
+    aliased_imports_match_exclusions,
+    r#"import { Button as Btn, Menu as M } from "@mui/material"; const a = <><Btn /><M.Item /><M /></>;"#,
+    r#"import { Button as Btn, Menu as M } from "@mui/material"; const a = <><Btn /><M.Item /><M data-source="input.js:1" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "rootOnly": true }"#)),
+    root_only_counts_children_not_attributes,
+    r#"const a = <div render={() => <Inner />}><span /></div>;"#,
+    r#"const a = <div render={() => <Inner data-source="input.js:1" />} data-source="input.js:1"><span /></div>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "rootOnly": true }"#)),
+    root_only_counts_children_not_props,
+    r#"import { jsx } from "react/jsx-runtime";
+jsx("div", { render: () => jsx(Inner, {}), children: jsx("span", {}) });
+React.createElement("div", { render: () => React.createElement(Inner) }, React.createElement("span"));"#,
+    r#"import { jsx } from "react/jsx-runtime";
+jsx("div", { render: () => jsx(Inner, { "data-source": "input.js:2" }), children: jsx("span", {}), "data-source": "input.js:2" });
+React.createElement("div", { render: () => React.createElement(Inner, { "data-source": "input.js:3" }), "data-source": "input.js:3" }, React.createElement("span"));"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, None),
+    create_element_from_react_or_preact,
+    r#"import { createElement } from "react";
+import { createElement as h } from "preact";
+import * as R from "react";
+createElement("div");
+h("div");
+R.createElement("div");"#,
+    r#"import { createElement } from "react";
+import { createElement as h } from "preact";
+import * as R from "react";
+createElement("div", { "data-source": "input.js:4" });
+h("div", { "data-source": "input.js:5" });
+R.createElement("div", { "data-source": "input.js:6" });"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, None),
+    create_element_from_elsewhere,
+    r#"import { createElement } from "./dom";
+import Reactive from "reactive";
+createElement("div");
+Reactive.createElement("div");
+reactDom.createElement("div");"#,
+    r#"import { createElement } from "./dom";
+import Reactive from "reactive";
+createElement("div");
+Reactive.createElement("div");
+reactDom.createElement("div");"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, None),
+    jsx_runtime_from_imports,
+    r#"import { jsxs as _jsxs } from "react/jsx-runtime";
+import * as runtime from "preact/jsx-dev-runtime";
+_jsxs("div", {});
+runtime.jsxDEV("div", {});
+_jsxRuntime.jsx("div", {});"#,
+    r#"import { jsxs as _jsxs } from "react/jsx-runtime";
+import * as runtime from "preact/jsx-dev-runtime";
+_jsxs("div", { "data-source": "input.js:3" });
+runtime.jsxDEV("div", { "data-source": "input.js:4" });
+_jsxRuntime.jsx("div", { "data-source": "input.js:5" });"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, None),
+    jsx_from_elsewhere,
+    r#"import { jsx as sql } from "./sql";
+function jsx(tpl, vals) {}
+jsx("select *", { id: 1 });
+sql("select *", { id: 1 });
+obj.jsx("x", { a: 1 });"#,
+    r#"import { jsx as sql } from "./sql";
+function jsx(tpl, vals) {}
+jsx("select *", { id: 1 });
+sql("select *", { id: 1 });
+obj.jsx("x", { a: 1 });"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "skipOnSpread": true }"#)),
+    skip_on_spread,
+    r#"const a = <><div {...p} /><div id="a" /></>;"#,
+    r#"const a = <><div {...p} /><div id="a" data-source="input.js:1" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "validHtmlTagsOnly": true, "customElements": ["my-widget"] }"#)
+    ),
+    valid_html_tags_only,
+    r#"const a = <><div /><blink2 /><my-widget /><x-y /><Button /><svg:rect /><svg:blink2 /></>;"#,
+    r#"const a = <><div data-source="input.js:1" /><blink2 /><my-widget data-source="input.js:1" /><x-y /><Button data-source="input.js:1" /><svg:rect data-source="input.js:1" /><svg:blink2 /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "excluded": ["Input"] }"#)),
+    exclusions_ignore_case_by_default,
+    r#"const a = <><Input /><input /></>;"#,
+    r#"const a = <><Input /><input /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(
+        t,
+        Some(r#"{ "excluded": ["Input"], "caseSensitiveExclusions": true }"#)
+    ),
+    case_sensitive_exclusions,
+    r#"const a = <><Input /><input /></>;"#,
+    r#"const a = <><Input /><input data-source="input.js:1" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "tagFragments": true }"#)),
+    tag_fragments,
+    r#"const a = <><Fragment key="a" /><React.Fragment><div /></React.Fragment></>;"#,
+    r#"const a = <><Fragment key="a" data-source="input.js:1" /><React.Fragment data-source="input.js:1"><div data-source="input.js:1" /></React.Fragment></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "ignoredMemberObjects": ["Styled"] }"#)),
+    ignored_member_objects_skip_their_members,
+    r#"const a = <><Styled.Button /><Styled.Card.Header /><Menu.Item /><Styled /><UnStyled.Button /></>;"#,
+    r#"const a = <><Styled.Button /><Styled.Card.Header /><Menu.Item data-source="input.js:1" /><Styled data-source="input.js:1" /><UnStyled.Button data-source="input.js:1" /></>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "includePropCount": true }"#)),
+    prop_count_excludes_injected_attributes,
+    r#"const a = <div id="a" {...rest}><span /></div>;"#,
+    r#"const a = <div id="a" {...rest} data-source-props="2" data-source="input.js:1"><span data-source-props="0" data-source="input.js:1" /></div>;"#
+);
+
+test_inline!(
+    common::syntax(),
+    |t| common::react_source(t, Some(r#"{ "includePropCount": true }"#)),
+    prop_count_excludes_compiled_children,
+    r#"import { jsx } from "react/jsx-runtime";
+const a = <div id>x</div>;
+jsx("div", { id, children });
+jsx("div", { id: "a", children: "x" });"#,
+    r#"import { jsx } from "react/jsx-runtime";
+const a = <div id data-source-props="1" data-source="input.js:2">x</div>;
+jsx("div", { id, children, "data-source-props": "1", "data-source": "input.js:3" });
+jsx("div", { id: "a", children: "x", "data-source-props": "1", "data-source": "input.js:4" });"#
+);
+
+/// Running the plugin again over its own output must not change anything.
+#[test]
+fn second_run_is_a_no_op() {
+    let configs = [
+        "{}",
+        r#"{ "includeColumn": true, "includeRange": true }"#,
+        r#"{ "splitAttributes": true, "includeColumn": true, "contentHash": true }"#,
+        r#"{ "attributeName": "data-loc", "format": "{path}@{line}" }"#,
+        r#"{ "attributePrefix": "acme", "includeComponentName": true }"#,
+        r#"{ "valueKind": "object" }"#,
+        r#"{ "includeElementId": true, "includeSelfClosing": true, "includePropCount": true }"#,
+        r#"{ "includeModule": true, "includeSnippet": true }"#,
+        r#"{ "libraries": { "lib": { "attribute": "data-lib" } } }"#,
+        r#"{ "prependAttribute": true, "overwriteExisting": true }"#,
+        r#"{ "spreadPrecedence": "theirs", "insertAfter": "id" }"#,
+        r#"{ "componentBoundaryOnly": true, "styledComponents": true }"#,
+        r#"{ "emitManifest": true }"#,
+        r#"{ "dryRun": true }"#,
+        r#"{ "emitAs": "comment" }"#,
+        r#"{ "emitAs": "comment", "includeComponentName": true, "styledComponents": true }"#,
+    ];
+    let src = r#"import { Button } from "lib";
+import { jsx as _jsx } from "react/jsx-runtime";
+import styled from "styled-components";
+const Title = styled.h1`color: red;`;
+export const App = (props) => (
+  <main id="app" {...props}>
+    <Button label="a" />
+    <hr />
+    <div {...props}>text</div>
+    {React.createElement("span", null)}
+    {_jsx("p", { id: "p" })}
+  </main>
+);"#;
+    for config in configs {
+        Tester::run(|tester| {
+            let syntax = common::syntax();
+            let pass = common::react_source(tester, Some(config));
+            let once = tester.apply_transform(pass, "once.tsx", syntax, Some(true), src)?;
+            let twice = (
+                common::react_source(tester, Some(config)),
+                common::react_source(tester, Some(config)),
+            );
+            let twice = tester.apply_transform(twice, "twice.tsx", syntax, Some(true), src)?;
+            let comments = tester.comments.clone();
+            let (once, twice) = (
+                tester.print(&once, &comments),
+                tester.print(&twice, &comments),
+            );
+            assert_eq!(once.replace("once", "twice"), twice, "config: {config}");
+            Ok(())
+        });
+    }
+}